@@ -0,0 +1,93 @@
+macro_rules! read_methods {
+    ($( $( #[$attr:meta] )* $name:ident() -> $ty:ty => $from_bytes:ident; )*) => {
+        $(
+            $( #[$attr] )*
+            #[inline]
+            pub fn $name(&mut self) -> Result<$ty, ()> {
+                self.read_array().map(<$ty>::$from_bytes)
+            }
+        )*
+    };
+}
+
+/// A cursor for reading values from the front of a byte buffer, created by
+/// [`crate::ArrayVec::reader()`].
+///
+/// Each read advances the cursor, failing with `Err(())` and leaving the
+/// cursor untouched if there aren't enough bytes left.
+///
+/// # Examples
+///
+/// ```rust
+/// use const_arrayvec::ArrayVec;
+/// let mut buffer: ArrayVec<u8, 16> = ArrayVec::new();
+/// buffer.put_u16_be(0xCAFE).unwrap();
+/// buffer.put_u32_le(42).unwrap();
+/// buffer.try_extend_from_slice(b"hi").unwrap();
+///
+/// let mut reader = buffer.reader();
+///
+/// assert_eq!(reader.read_u16_be(), Ok(0xCAFE));
+/// assert_eq!(reader.read_u32_le(), Ok(42));
+/// assert_eq!(reader.read_bytes(2), Ok(b"hi".as_ref()));
+/// assert_eq!(reader.read_u8(), Err(()));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ByteReader<'a> {
+    remaining: &'a [u8],
+}
+
+// running out of bytes is the only way a read can fail, so there's nothing
+// more useful to put in the error
+#[allow(clippy::result_unit_err)]
+impl<'a> ByteReader<'a> {
+    read_methods! {
+        /// Read a `u16` in big-endian byte order.
+        read_u16_be() -> u16 => from_be_bytes;
+        /// Read a `u16` in little-endian byte order.
+        read_u16_le() -> u16 => from_le_bytes;
+        /// Read a `u32` in big-endian byte order.
+        read_u32_be() -> u32 => from_be_bytes;
+        /// Read a `u32` in little-endian byte order.
+        read_u32_le() -> u32 => from_le_bytes;
+        /// Read a `u64` in big-endian byte order.
+        read_u64_be() -> u64 => from_be_bytes;
+        /// Read a `u64` in little-endian byte order.
+        read_u64_le() -> u64 => from_le_bytes;
+    }
+
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        ByteReader { remaining: bytes }
+    }
+
+    /// The bytes which haven't been read yet.
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] { self.remaining }
+
+    /// Read the next `count` bytes.
+    pub fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], ()> {
+        if self.remaining.len() < count {
+            return Err(());
+        }
+
+        let (bytes, rest) = self.remaining.split_at(count);
+        self.remaining = rest;
+
+        Ok(bytes)
+    }
+
+    /// Read a single byte.
+    #[inline]
+    pub fn read_u8(&mut self) -> Result<u8, ()> {
+        self.read_bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn read_array<const K: usize>(&mut self) -> Result<[u8; K], ()> {
+        let bytes = self.read_bytes(K)?;
+
+        let mut array = [0; K];
+        array.copy_from_slice(bytes);
+
+        Ok(array)
+    }
+}
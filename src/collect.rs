@@ -0,0 +1,36 @@
+use crate::{ArrayVec, CapacityError};
+
+/// An extension trait for collecting an [`Iterator`] into an [`ArrayVec`]
+/// without panicking when there are too many items.
+///
+/// # Examples
+///
+/// ```rust
+/// use const_arrayvec::{ArrayVec, CapacityError, CollectArrayVec};
+///
+/// let small = (0..3).try_collect_arrayvec::<4>().unwrap();
+/// assert_eq!(&small, [0, 1, 2].as_ref());
+///
+/// let too_big = (0..10).try_collect_arrayvec::<4>();
+/// assert_eq!(too_big, Err(CapacityError(())));
+/// ```
+pub trait CollectArrayVec: Iterator {
+    /// Collect every item into an [`ArrayVec`], failing if there isn't
+    /// enough room.
+    fn try_collect_arrayvec<const N: usize>(
+        self,
+    ) -> Result<ArrayVec<Self::Item, { N }>, CapacityError<()>>
+    where
+        Self: Sized,
+    {
+        let mut vector = ArrayVec::new();
+
+        for item in self {
+            vector.try_push(item).map_err(|_| CapacityError(()))?;
+        }
+
+        Ok(vector)
+    }
+}
+
+impl<I: Iterator> CollectArrayVec for I {}
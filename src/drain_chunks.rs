@@ -0,0 +1,88 @@
+use crate::ArrayVec;
+use core::{
+    iter::{ExactSizeIterator, FusedIterator},
+    ptr,
+};
+
+/// An iterator which removes `K` items at a time from the front of an
+/// [`ArrayVec`], created by [`ArrayVec::drain_chunks()`].
+///
+/// Any items which weren't yielded (including the leftovers when the length
+/// isn't a multiple of `K`) are kept in the vector.
+#[derive(Debug)]
+pub struct DrainChunks<'a, T, const N: usize, const K: usize> {
+    inner: &'a mut ArrayVec<T, { N }>,
+    /// The index of the first item which hasn't been yielded.
+    head: usize,
+    /// The vector's original length.
+    length: usize,
+}
+
+impl<'a, T, const N: usize, const K: usize> DrainChunks<'a, T, { N }, { K }> {
+    pub(crate) fn new(vector: &'a mut ArrayVec<T, { N }>) -> Self {
+        assert!(K != 0, "The chunk size must be non-zero");
+
+        let length = vector.len();
+
+        unsafe {
+            // prevent a leaked DrainChunks from letting users read items
+            // which have been moved out
+            vector.set_len(0);
+        }
+
+        DrainChunks {
+            inner: vector,
+            head: 0,
+            length,
+        }
+    }
+}
+
+impl<'a, T, const N: usize, const K: usize> Iterator
+    for DrainChunks<'a, T, { N }, { K }>
+{
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.length - self.head < K {
+            return None;
+        }
+
+        unsafe {
+            // copy the next K items onto the stack, taking ownership
+            let chunk = self.inner.as_ptr().add(self.head) as *const [T; K];
+            self.head += K;
+            Some(chunk.read())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl<'a, T, const N: usize, const K: usize> ExactSizeIterator
+    for DrainChunks<'a, T, { N }, { K }>
+{
+    fn len(&self) -> usize { (self.length - self.head) / K }
+}
+
+impl<'a, T, const N: usize, const K: usize> FusedIterator
+    for DrainChunks<'a, T, { N }, { K }>
+{
+}
+
+impl<'a, T, const N: usize, const K: usize> Drop
+    for DrainChunks<'a, T, { N }, { K }>
+{
+    fn drop(&mut self) {
+        let remaining = self.length - self.head;
+
+        unsafe {
+            // move the items we didn't yield back to the start
+            let src = self.inner.as_ptr().add(self.head);
+            ptr::copy(src, self.inner.as_mut_ptr(), remaining);
+            self.inner.set_len(remaining);
+        }
+    }
+}
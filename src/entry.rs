@@ -0,0 +1,99 @@
+use crate::ArrayVec;
+
+/// A view into a single position in an [`ArrayVec`], which may either hold
+/// an item or be the spot just past the end.
+///
+/// This is created by [`ArrayVec::entry()`].
+#[derive(Debug)]
+pub enum Entry<'a, T, const N: usize> {
+    /// The index refers to an existing item.
+    Occupied(OccupiedEntry<'a, T, { N }>),
+    /// The index is the vector's length, so an item can be pushed there.
+    Vacant(VacantEntry<'a, T, { N }>),
+}
+
+impl<'a, T, const N: usize> Entry<'a, T, { N }> {
+    pub(crate) fn new(
+        vector: &'a mut ArrayVec<T, { N }>,
+        index: usize,
+    ) -> Self {
+        if index < vector.len() {
+            Entry::Occupied(OccupiedEntry { vector, index })
+        } else {
+            Entry::Vacant(VacantEntry { vector })
+        }
+    }
+
+    /// The index this entry refers to.
+    pub fn index(&self) -> usize {
+        match self {
+            Entry::Occupied(entry) => entry.index(),
+            Entry::Vacant(entry) => entry.index(),
+        }
+    }
+
+    /// Get the existing item, or push `item` if the entry is vacant.
+    ///
+    /// # Panics
+    ///
+    /// The entry is vacant and the vector is already full.
+    #[inline]
+    pub fn or_insert(self, item: T) -> &'a mut T {
+        self.or_insert_with(|| item)
+    }
+
+    /// Get the existing item, or push the result of `f()` if the entry is
+    /// vacant.
+    ///
+    /// # Panics
+    ///
+    /// The entry is vacant and the vector is already full.
+    pub fn or_insert_with<F: FnOnce() -> T>(self, f: F) -> &'a mut T {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(f()),
+        }
+    }
+}
+
+/// An [`Entry`] referring to an existing item.
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, T, const N: usize> {
+    vector: &'a mut ArrayVec<T, { N }>,
+    index: usize,
+}
+
+impl<'a, T, const N: usize> OccupiedEntry<'a, T, { N }> {
+    /// The index of the item.
+    pub fn index(&self) -> usize { self.index }
+
+    pub fn get(&self) -> &T { &self.vector[self.index] }
+
+    pub fn get_mut(&mut self) -> &mut T { &mut self.vector[self.index] }
+
+    /// Convert the entry into a mutable reference which lives as long as the
+    /// borrow of the original vector.
+    pub fn into_mut(self) -> &'a mut T { &mut self.vector[self.index] }
+}
+
+/// An [`Entry`] referring to the position just past the end of the vector.
+#[derive(Debug)]
+pub struct VacantEntry<'a, T, const N: usize> {
+    vector: &'a mut ArrayVec<T, { N }>,
+}
+
+impl<'a, T, const N: usize> VacantEntry<'a, T, { N }> {
+    /// The index an inserted item would have (i.e. the vector's length).
+    pub fn index(&self) -> usize { self.vector.len() }
+
+    /// Push an item onto the end of the vector, returning a reference to it.
+    ///
+    /// # Panics
+    ///
+    /// The vector is already full.
+    pub fn insert(self, item: T) -> &'a mut T {
+        let index = self.vector.len();
+        self.vector.push(item);
+        &mut self.vector[index]
+    }
+}
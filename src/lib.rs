@@ -2,16 +2,33 @@
 #![feature(const_generics)]
 #![allow(incomplete_features)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod byte_reader;
+mod collect;
 mod drain;
+mod drain_chunks;
+mod entry;
+mod ring;
+mod utf8_lossy;
 
+pub use byte_reader::ByteReader;
+pub use collect::CollectArrayVec;
 pub use drain::Drain;
+pub use drain_chunks::DrainChunks;
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+pub use ring::RingArrayVec;
+pub use utf8_lossy::Utf8Lossy;
 
 use core::{
-    cmp::Ordering,
+    cmp::{self, Ordering},
     fmt::{self, Debug, Display, Formatter},
     hash::{Hash, Hasher},
+    iter::{Enumerate, Sum},
+    marker::PhantomData,
     mem::{self, MaybeUninit},
-    ops::{Deref, DerefMut, Index, IndexMut, Range},
+    ops::{AddAssign, Deref, DerefMut, Index, IndexMut, Mul, Range},
     ptr, slice,
 };
 
@@ -28,6 +45,72 @@ macro_rules! out_of_bounds {
     };
 }
 
+macro_rules! capacity_exceeded {
+    ($method:expr, $count:expr, $capacity:expr) => {
+        panic!(
+            concat!(
+                "ArrayVec::",
+                $method,
+                "(): {} items won't fit in a vector with capacity {}"
+            ),
+            $count, $capacity
+        )
+    };
+}
+
+macro_rules! put_methods {
+    ($( $( #[$attr:meta] )* $name:ident($ty:ty) => $to_bytes:ident; )*) => {
+        $(
+            $( #[$attr] )*
+            #[inline]
+            pub fn $name(
+                &mut self,
+                value: $ty,
+            ) -> Result<(), CapacityError<()>> {
+                self.try_extend_from_slice(&value.$to_bytes())
+            }
+        )*
+    };
+}
+
+/// Assert at compile time that an [`ArrayVec`] type has a particular
+/// capacity.
+///
+/// This is useful for making sure a type alias doesn't accidentally change
+/// size.
+///
+/// # Examples
+///
+/// ```rust
+/// use const_arrayvec::{assert_capacity, ArrayVec};
+///
+/// type Buffer = ArrayVec<u8, 16>;
+///
+/// assert_capacity!(Buffer, 16);
+/// ```
+///
+/// A mismatch is a compile error.
+///
+/// ```rust,compile_fail
+/// use const_arrayvec::{assert_capacity, ArrayVec};
+///
+/// type Buffer = ArrayVec<u8, 16>;
+///
+/// assert_capacity!(Buffer, 8);
+/// ```
+#[macro_export]
+macro_rules! assert_capacity {
+    ($vector:ty, $capacity:expr $(,)?) => {
+        // the array lengths (and therefore types) only match if the
+        // capacities are the same
+        const _: [(); $capacity] = [(); <$vector>::CAPACITY];
+    };
+}
+
+/// Get the capacity, `N`, of an `ArrayVec<T, N>` in a `const` context.
+#[inline]
+pub const fn capacity_of<T, const N: usize>() -> usize { N }
+
 /// A vector type backed by a fixed-length array.
 pub struct ArrayVec<T, const N: usize> {
     items: [MaybeUninit<T>; N],
@@ -35,9 +118,12 @@ pub struct ArrayVec<T, const N: usize> {
 }
 
 impl<T, const N: usize> ArrayVec<T, { N }> {
+    /// The maximum number of items the vector can hold.
+    pub const CAPACITY: usize = N;
+
     /// Create a new, empty [`ArrayVec`].
     #[inline]
-    pub fn new() -> ArrayVec<T, { N }> {
+    pub const fn new() -> ArrayVec<T, { N }> {
         unsafe {
             ArrayVec {
                 // this is safe because we've asked for a big block of
@@ -50,6 +136,124 @@ impl<T, const N: usize> ArrayVec<T, { N }> {
         }
     }
 
+    /// Create a full [`ArrayVec`] from an array, in a way that can be used in
+    /// `const` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    ///
+    /// const TABLE: ArrayVec<u8, 4> = ArrayVec::from_array_const([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(&TABLE, [1, 2, 3, 4].as_ref());
+    /// ```
+    pub const fn from_array_const(array: [T; N]) -> ArrayVec<T, { N }>
+    where
+        T: Copy,
+    {
+        /// Lets us reinterpret the array without needing a non-const
+        /// transmute.
+        union Items<T: Copy, const N: usize> {
+            array: [T; N],
+            uninit: [MaybeUninit<T>; N],
+        }
+
+        ArrayVec {
+            // Note: Safe because a [T; N] is identical to
+            // [MaybeUninit<T>; N]
+            items: unsafe { Items { array }.uninit },
+            length: N,
+        }
+    }
+
+    /// Create a new [`ArrayVec`] containing `pattern` repeated `times` times.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    ///
+    /// let vector: ArrayVec<u32, 6> =
+    ///     ArrayVec::try_from_repeated_slice(&[1, 2], 3).unwrap();
+    /// assert_eq!(&vector, [1, 2, 1, 2, 1, 2].as_ref());
+    ///
+    /// let too_big = ArrayVec::<u32, 6>::try_from_repeated_slice(&[1, 2], 4);
+    /// assert_eq!(too_big, Err(CapacityError(())));
+    ///
+    /// // repeating an empty pattern any number of times gives an empty vector
+    /// let empty: ArrayVec<u32, 6> =
+    ///     ArrayVec::try_from_repeated_slice(&[], usize::MAX).unwrap();
+    /// assert!(empty.is_empty());
+    /// ```
+    pub fn try_from_repeated_slice(
+        pattern: &[T],
+        times: usize,
+    ) -> Result<ArrayVec<T, { N }>, CapacityError<()>>
+    where
+        T: Copy,
+    {
+        if pattern.is_empty() || times == 0 {
+            // there's nothing to copy, so don't bother looping
+            return Ok(ArrayVec::new());
+        }
+
+        match pattern.len().checked_mul(times) {
+            Some(length) if length <= N => {},
+            _ => return Err(CapacityError(())),
+        }
+
+        let mut vector = ArrayVec::new();
+
+        for _ in 0..times {
+            // Note: we've already checked the total length will fit
+            vector.try_extend_from_slice(pattern)?;
+        }
+
+        Ok(vector)
+    }
+
+    /// Create a new [`ArrayVec`] by letting a closure initialize the backing
+    /// array directly.
+    ///
+    /// The closure is given every slot in the backing array and returns the
+    /// number of leading items it initialized, which becomes the length.
+    ///
+    /// # Safety
+    ///
+    /// The closure must return a count no greater than `N`, and the first
+    /// `count` slots must have been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let vector: ArrayVec<u32, 5> = unsafe {
+    ///     ArrayVec::build_with(|slots: &mut [MaybeUninit<u32>]| {
+    ///         for (i, slot) in slots.iter_mut().take(3).enumerate() {
+    ///             *slot = MaybeUninit::new(i as u32 * 10);
+    ///         }
+    ///         3
+    ///     })
+    /// };
+    ///
+    /// assert_eq!(&vector, [0, 10, 20].as_ref());
+    /// ```
+    pub unsafe fn build_with<F>(f: F) -> ArrayVec<T, { N }>
+    where
+        F: FnOnce(&mut [MaybeUninit<T>]) -> usize,
+    {
+        let mut vector = ArrayVec::new();
+
+        let count = f(&mut vector.items);
+        debug_assert!(count <= N, "The closure initialized too many items");
+        vector.set_len(count);
+
+        vector
+    }
+
     #[inline]
     pub const fn len(&self) -> usize { self.length }
 
@@ -67,8 +271,35 @@ impl<T, const N: usize> ArrayVec<T, { N }> {
     #[inline]
     pub const fn is_full(&self) -> bool { self.len() >= self.capacity() }
 
+    /// The total size of an [`ArrayVec`] of this type in bytes, including
+    /// its length field and any unused capacity.
+    #[inline]
+    pub const fn mem_footprint() -> usize {
+        mem::size_of::<ArrayVec<T, { N }>>()
+    }
+
+    /// The number of bytes taken up by the items currently in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<u32, 8> = ArrayVec::new();
+    /// vector.try_extend_from_slice(&[1, 2, 3]).unwrap();
+    ///
+    /// assert_eq!(vector.bytes_used(), 12);
+    /// assert!(ArrayVec::<u32, 8>::mem_footprint() >= 32);
+    /// ```
+    #[inline]
+    pub const fn bytes_used(&self) -> usize { self.len() * mem::size_of::<T>() }
+
+    /// The number of items in the backing array (i.e. `N`), regardless of
+    /// how many are initialized.
+    #[inline]
+    pub const fn as_uninit_len(&self) -> usize { N }
+
     #[inline]
-    pub fn as_ptr(&self) -> *const T { self.items.as_ptr() as *const T }
+    pub const fn as_ptr(&self) -> *const T { self.items.as_ptr() as *const T }
 
     #[inline]
     pub fn as_mut_ptr(&mut self) -> *mut T { self.items.as_mut_ptr() as *mut T }
@@ -121,6 +352,81 @@ impl<T, const N: usize> ArrayVec<T, { N }> {
         }
     }
 
+    /// Push a default value onto the end of the vector, returning a
+    /// reference to it so it can be filled in.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector: ArrayVec<(u32, String), 1> = ArrayVec::new();
+    ///
+    /// let slot = vector.push_default().unwrap();
+    /// slot.0 = 42;
+    /// slot.1.push_str("Hello");
+    /// assert_eq!(vector[0], (42, "Hello".to_owned()));
+    ///
+    /// assert_eq!(vector.push_default(), Err(CapacityError(())));
+    /// ```
+    pub fn push_default(&mut self) -> Result<&mut T, CapacityError<()>>
+    where
+        T: Default,
+    {
+        if self.is_full() {
+            return Err(CapacityError(()));
+        }
+
+        let index = self.len();
+
+        unsafe {
+            self.push_unchecked(T::default());
+        }
+
+        Ok(&mut self[index])
+    }
+
+    /// Try to move every item in an array onto the end of the vector,
+    /// returning the original array if there isn't enough room.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector: ArrayVec<u32, 8> = ArrayVec::new();
+    /// vector.try_extend_from_slice(&[1, 2, 3]).unwrap();
+    ///
+    /// vector.try_push_array([4, 5]).unwrap();
+    /// assert_eq!(&vector, [1, 2, 3, 4, 5].as_ref());
+    ///
+    /// let got = vector.try_push_array([6, 7, 8, 9]);
+    /// assert_eq!(got, Err(CapacityError([6, 7, 8, 9])));
+    /// assert_eq!(vector.len(), 5);
+    /// ```
+    pub fn try_push_array<const M: usize>(
+        &mut self,
+        items: [T; M],
+    ) -> Result<(), CapacityError<[T; M]>> {
+        if self.remaining_capacity() < M {
+            return Err(CapacityError(items));
+        }
+
+        let len = self.len();
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                items.as_ptr(),
+                self.as_mut_ptr().add(len),
+                M,
+            );
+            // ownership has been transferred to the vector, make sure the
+            // array's destructors aren't called
+            mem::forget(items);
+            self.set_len(len + M);
+        }
+
+        Ok(())
+    }
+
     /// Add an item to the end of the array without checking the capacity.
     ///
     /// # Safety
@@ -154,6 +460,40 @@ impl<T, const N: usize> ArrayVec<T, { N }> {
         self.length = new_length;
     }
 
+    /// Set the vector's length without dropping or moving out elements,
+    /// failing if `new_length` is larger than the capacity.
+    ///
+    /// # Safety
+    ///
+    /// Unlike [`ArrayVec::set_len()`] the length is always checked against
+    /// the capacity, but the first `new_length` items must still be
+    /// initialized. Any items past `new_length` will be leaked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// unsafe {
+    ///     assert_eq!(vector.set_len_checked(2), Ok(()));
+    ///     assert_eq!(vector.set_len_checked(4), Err(CapacityError(())));
+    /// }
+    ///
+    /// assert_eq!(&vector, [1, 2].as_ref());
+    /// ```
+    pub unsafe fn set_len_checked(
+        &mut self,
+        new_length: usize,
+    ) -> Result<(), CapacityError<()>> {
+        if new_length > N {
+            return Err(CapacityError(()));
+        }
+
+        self.set_len(new_length);
+        Ok(())
+    }
+
     /// Remove an item from the end of the vector.
     ///
     /// # Examples
@@ -184,6 +524,68 @@ impl<T, const N: usize> ArrayVec<T, { N }> {
         }
     }
 
+    /// Get a mutable reference to the last item, using `f()` to push a new
+    /// one if the vector is empty.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the vector is empty and has no capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut groups: ArrayVec<u32, 4> = ArrayVec::new();
+    ///
+    /// // the vector is empty so a new group gets started
+    /// *groups.last_mut_or_push_with(|| 0) += 1;
+    /// assert_eq!(&groups, [1].as_ref());
+    ///
+    /// // otherwise we keep adding to the last one
+    /// *groups.last_mut_or_push_with(|| 0) += 1;
+    /// assert_eq!(&groups, [2].as_ref());
+    /// ```
+    pub fn last_mut_or_push_with<F: FnOnce() -> T>(&mut self, f: F) -> &mut T {
+        if self.is_empty() {
+            self.push(f());
+        }
+
+        let last = self.len() - 1;
+        &mut self[last]
+    }
+
+    /// Get the [`Entry`] at `index`, which is either an existing item or the
+    /// spot just past the end of the vector.
+    ///
+    /// # Panics
+    ///
+    /// The index is greater than the vector's length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, Entry};
+    /// let mut vector: ArrayVec<u32, 4> = ArrayVec::new();
+    ///
+    /// match vector.entry(0) {
+    ///     Entry::Vacant(entry) => *entry.insert(1) += 1,
+    ///     Entry::Occupied(_) => unreachable!(),
+    /// }
+    /// assert_eq!(&vector, [2].as_ref());
+    ///
+    /// *vector.entry(0).or_insert(42) *= 10;
+    /// assert_eq!(&vector, [20].as_ref());
+    /// ```
+    pub fn entry(&mut self, index: usize) -> Entry<'_, T, { N }> {
+        let len = self.len();
+
+        if index > len {
+            out_of_bounds!("entry", index, len);
+        }
+
+        Entry::new(self, index)
+    }
+
     /// Shorten the vector, keeping the first `new_length` elements and dropping
     /// the rest.
     pub fn truncate(&mut self, new_length: usize) {
@@ -202,607 +604,4563 @@ impl<T, const N: usize> ArrayVec<T, { N }> {
         }
     }
 
-    /// Remove all items from the vector.
-    #[inline]
-    pub fn clear(&mut self) { self.truncate(0); }
-
-    /// Insert an item.
-    ///
-    /// # Panics
+    /// Shorten the vector to `new_length` items, failing if that would
+    /// require growing it.
     ///
-    /// The vector must have enough space for the item (see
-    /// [`ArrayVec::remaining_capacity()`]).
-    pub fn insert(&mut self, index: usize, item: T) {
-        match self.try_insert(index, item) {
-            Ok(_) => {},
-            Err(e) => panic!("Insert failed: {}", e),
-        }
-    }
-
-    /// Try to insert an item into the vector.
+    /// This is a safe alternative to [`ArrayVec::set_len()`] for when the
+    /// length comes from somewhere untrusted.
     ///
     /// # Examples
     ///
-    /// The "happy path" works just as expected:
-    ///
-    /// ```rust
-    /// use const_arrayvec::ArrayVec;
-    /// let mut vector: ArrayVec<u32, 5> = ArrayVec::new();
-    /// vector.push(12);
-    /// vector.push(34);
-    ///
-    /// vector.try_insert(1, 56).unwrap();
-    ///
-    /// assert_eq!(vector.as_slice(), &[12, 56, 34]);
-    /// ```
-    ///
-    /// Trying to insert an item when the [`ArrayVec`] is full will fail,
-    /// returning the original item.
-    ///
     /// ```rust
     /// use const_arrayvec::{ArrayVec, CapacityError};
     /// let mut vector = ArrayVec::from([1, 2, 3]);
-    /// println!("{}, {}", vector.len(), vector.capacity());
-    /// println!("{:?}", vector);
-    /// assert!(vector.is_full());
     ///
-    /// let got = vector.try_insert(1, 7);
+    /// assert_eq!(vector.try_truncate_or_err(2), Ok(()));
+    /// assert_eq!(&vector, [1, 2].as_ref());
     ///
-    /// assert_eq!(got, Err(CapacityError(7)));
+    /// // there's no way to initialize the new items, so growing is an error
+    /// assert_eq!(vector.try_truncate_or_err(3), Err(CapacityError(())));
+    /// assert_eq!(&vector, [1, 2].as_ref());
     /// ```
-    pub fn try_insert(
+    pub fn try_truncate_or_err(
         &mut self,
-        index: usize,
-        item: T,
-    ) -> Result<(), CapacityError<T>> {
-        let len = self.len();
-
-        // bounds checks
-        if index > len {
-            out_of_bounds!("try_insert", index, len);
-        }
-        if self.is_full() {
-            return Err(CapacityError(item));
-        }
-
-        unsafe {
-            self.insert_unchecked(index, item);
+        new_length: usize,
+    ) -> Result<(), CapacityError<()>> {
+        if new_length > self.len() {
+            return Err(CapacityError(()));
         }
 
+        self.truncate(new_length);
         Ok(())
     }
 
-    /// Insert an item into the vector, removing and returning its last
-    /// item if already full.
-    ///
-    /// # Panics
+    /// Remove all items from the vector.
+    #[inline]
+    pub fn clear(&mut self) { self.truncate(0); }
+
+    /// Move every item out of the vector and into a callback, leaving the
+    /// vector empty.
     ///
-    /// The item cannot be inserted at an index greater than the
-    /// vector's length or greater or equal to the maximum capacity `N`.
+    /// If the callback panics, the items which haven't been passed to it
+    /// yet will still be dropped.
     ///
     /// # Examples
     ///
-    /// When the vector's not full, [`ArrayVec::force_insert`] acts like
-    /// [`ArrayVec::insert`]:
-    ///
     /// ```rust
     /// use const_arrayvec::ArrayVec;
-    /// let mut vector: ArrayVec<u8, 5> = ArrayVec::new();
+    /// let mut vector = ArrayVec::from(["a".to_owned(), "b".to_owned()]);
+    /// let mut seen = Vec::new();
     ///
-    /// assert_eq!(vector.force_insert(0, 42), None);
-    /// assert_eq!(vector.force_insert(0, 24), None);
-    /// assert_eq!(&vector, [24, 42].as_ref());
-    /// ```
+    /// vector.drain_each(|item| seen.push(item));
     ///
-    /// But when the vector's full, we get a different result:
-    ///
-    /// ```rust
-    /// use const_arrayvec::ArrayVec;
-    /// let mut vector = ArrayVec::from([
-    ///     "He".to_owned(),
-    ///     "ya".to_owned(),
-    /// ]);
-    ///
-    /// let out = vector
-    ///     .force_insert(1, "llo".to_owned())
-    ///     .unwrap();
-    ///
-    /// assert_eq!(&out, "ya");
-    /// assert_eq!(&vector, ["He".to_owned(), "llo".to_owned()].as_ref());
+    /// assert!(vector.is_empty());
+    /// assert_eq!(seen, vec!["a".to_owned(), "b".to_owned()]);
     /// ```
-    pub fn force_insert(&mut self, index: usize, item: T) -> Option<T> {
-        let len = self.len();
+    pub fn drain_each<F: FnMut(T)>(&mut self, mut f: F) {
+        /// Drops any items which haven't been moved out yet.
+        struct DropRemaining<T> {
+            start: *mut T,
+            next: usize,
+            len: usize,
+        }
 
-        let result;
+        impl<T> Drop for DropRemaining<T> {
+            fn drop(&mut self) {
+                unsafe {
+                    let remaining = ptr::slice_from_raw_parts_mut(
+                        self.start.add(self.next),
+                        self.len - self.next,
+                    );
+                    ptr::drop_in_place(remaining);
+                }
+            }
+        }
 
-        if index > len || index == N {
-            // Failed bound checks.
-            out_of_bounds!("force_insert", index, len);
-        } else if self.is_full() {
-            // The last item must be removed to perform the insertion.
+        let len = self.len();
 
-            unsafe {
-                // Store the last item before it's removed.
-                result = Some(ptr::read(self.as_ptr().add(len - 1)));
+        unsafe {
+            // the items are about to be moved out, so make sure the vector
+            // forgets about them
+            self.set_len(0);
 
-                // The last element should be removed so we shouldn't
-                // copy it.
-                self.insert_unchecked_keep_len(index, item, len - 1);
-            }
-        } else {
-            // Since nothing's going to be removed, the vector's size
-            // is going to be increased and nothing will be returned.
-            unsafe {
-                self.insert_unchecked(index, item);
+            let mut remaining = DropRemaining {
+                start: self.as_mut_ptr(),
+                next: 0,
+                len,
+            };
+
+            while remaining.next < remaining.len {
+                let item = remaining.start.add(remaining.next).read();
+                remaining.next += 1;
+                f(item);
             }
-            result = None;
         }
-
-        result
     }
 
-    /// Insert an item into the vector without checking if the index is
-    /// valid or if the vector isn't full.
+    /// Remove all items from the vector by resetting its length, without
+    /// visiting each item to run its destructor.
     ///
-    /// # Safety
+    /// This is restricted to `Copy` types because they can't have
+    /// destructors, so there's nothing to leak. For other types, use
+    /// [`ArrayVec::clear()`] or explicitly [`mem::forget()`] the items.
     ///
-    /// If you plan on using this function, you need to check for the
-    /// 2 previously mentioned conditions yourself before calling this
-    /// method.
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut buffer = ArrayVec::from([1_u8, 2, 3, 4]);
+    ///
+    /// buffer.clear_no_drop();
+    /// assert!(buffer.is_empty());
+    ///
+    /// // the buffer can be reused like normal
+    /// buffer.push(5);
+    /// assert_eq!(&buffer, [5].as_ref());
+    /// ```
     #[inline]
-    pub unsafe fn insert_unchecked(&mut self, index: usize, item: T) {
-        let len = self.len();
-        self.insert_unchecked_keep_len(index, item, len);
-        self.set_len(len + 1);
+    pub fn clear_no_drop(&mut self)
+    where
+        T: Copy,
+    {
+        unsafe {
+            // Note: Copy types can't implement Drop so this doesn't leak
+            self.set_len(0);
+        }
     }
 
-    /// Insert an item into the vector without checking if the index is
-    /// valid or if the vector isn't full or the vector's length and
-    /// without incrementing the vector's length.
+    /// Keep the longest prefix of items satisfying `predicate`, dropping
+    /// everything from the first item which doesn't.
     ///
-    /// # Safety
+    /// # Examples
     ///
-    /// If you plan on using this function, you need to check for the
-    /// 3 previously mentioned conditions yourself before calling this
-    /// method. You also need to increment the vector's length afterward
-    /// yourself.
-    unsafe fn insert_unchecked_keep_len(
-        &mut self,
-        index: usize,
-        item: T,
-        len: usize,
-    ) {
-        // The spot to put the new value at.
-        let ptr_index = self.as_mut_ptr().add(index);
-        // Shift everything over to make space. (Duplicating the
-        // `index`th element into two consecutive places.)
-        ptr::copy(ptr_index, ptr_index.add(1), len - index);
-        // Write it in, overwriting the first copy of the `index`th
-        // element.
-        ptr::write(ptr_index, item);
-    }
-
-    /// Remove the value contained at `index` and return it.
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3, 0, 5]);
     ///
-    /// # Panics
+    /// vector.truncate_while(|&x| x != 0);
     ///
-    /// The index is out of bounds.
+    /// assert_eq!(&vector, [1, 2, 3].as_ref());
+    /// ```
+    pub fn truncate_while<F: FnMut(&T) -> bool>(&mut self, mut predicate: F) {
+        let keep = self
+            .iter()
+            .position(|item| !predicate(item))
+            .unwrap_or_else(|| self.len());
+
+        self.truncate(keep);
+    }
+
+    /// Only keep the items which satisfy a predicate, preserving their order.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use const_arrayvec::ArrayVec;
-    /// let mut vector = ArrayVec::from([4, 3, 2]);
+    /// let mut vector = ArrayVec::from([1, 2, 3, 4]);
     ///
-    /// let three = vector.remove(1);
+    /// vector.retain(|x| x % 2 == 1);
     ///
-    /// assert_eq!(three, 3);
-    /// assert_eq!(&vector, [4, 2].as_ref());
+    /// assert_eq!(&vector, [1, 3].as_ref());
     /// ```
-    pub fn remove(&mut self, index: usize) -> T {
-        match self.try_remove(index) {
-            Some(item) => item,
-            None => out_of_bounds!("remove", index, self.len()),
-        }
+    #[inline]
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.retain_count(f);
     }
 
-    /// If `index` is in bounds, remove the value contained at `index`
-    /// and return it.
+    /// Only keep the items which satisfy a predicate (see
+    /// [`ArrayVec::retain()`]), returning the number of items removed.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use const_arrayvec::ArrayVec;
-    /// let mut vector = ArrayVec::from([4, 3, 2]);
+    /// let mut vector = ArrayVec::from([1, 2, 3, 4]);
     ///
-    /// let three = vector.try_remove(1);
-    /// let what = vector.try_remove(24);
+    /// let removed = vector.retain_count(|x| x % 2 == 1);
     ///
-    /// assert_eq!(three, Some(3));
-    /// assert_eq!(what, None);
-    /// assert_eq!(&vector, [4, 2].as_ref());
+    /// assert_eq!(removed, 2);
+    /// assert_eq!(&vector, [1, 3].as_ref());
     /// ```
-    #[inline]
-    pub fn try_remove(&mut self, index: usize) -> Option<T> {
-        if index < self.len() {
-            Some(unsafe { self.remove_unchecked(index) })
-        } else {
-            None
-        }
+    pub fn retain_count<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> usize {
+        let len = self.len();
+        self.retain_enumerate(|_, item| f(item));
+
+        len - self.len()
     }
 
-    /// Remove the value contained at `index` and return it.
+    /// Only keep the items which satisfy a predicate, giving the predicate
+    /// mutable access to each item along with its original index.
     ///
-    /// # Safety
+    /// # Examples
     ///
-    /// The index must be in bounds.
-    pub unsafe fn remove_unchecked(&mut self, index: usize) -> T {
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from(['a', 'b', 'c', 'd']);
+    ///
+    /// vector.retain_enumerate(|index, letter| {
+    ///     letter.make_ascii_uppercase();
+    ///     index % 2 == 1
+    /// });
+    ///
+    /// assert_eq!(&vector, ['B', 'D'].as_ref());
+    /// ```
+    pub fn retain_enumerate<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &mut T) -> bool,
+    {
         let len = self.len();
+        let mut removed = 0;
 
-        // Where the value to remove is.
-        let ptr_index = self.as_mut_ptr().add(index);
-        // Read the value before sending it to the other world.
-        let item = ptr::read(ptr_index);
-        // Shift every value after the removed one to the left.
-        ptr::copy(ptr_index.add(1), ptr_index, len - index - 1);
-        // We removed an item, so the length should be decremented.
-        self.set_len(len - 1);
+        {
+            let items = self.as_slice_mut();
 
-        item
+            for i in 0..len {
+                if !f(i, &mut items[i]) {
+                    removed += 1;
+                } else if removed > 0 {
+                    // shuffle the item back into the first free spot. If the
+                    // predicate panics we'll just leave the items jumbled up
+                    items.swap(i - removed, i);
+                }
+            }
+        }
+
+        // the rejected items have all been moved to the end
+        self.truncate(len - removed);
     }
 
-    /// Remove the value contained at `index` and return it without
-    /// conserving order.
-    ///
-    /// The removed value is replaced by the last value making this an
-    /// `O(1)` operation.
+    /// Only keep the items which are also present in `other`, preserving
+    /// their order.
     ///
-    /// # Panics
-    ///
-    /// The index is out of bounds.
+    /// This does a linear search of `other` for every item, so it's best
+    /// suited to small inputs.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use const_arrayvec::ArrayVec;
-    /// let mut vector = ArrayVec::from([1, 2, 4]);
+    /// let mut vector = ArrayVec::from([1, 2, 3, 4]);
     ///
-    /// assert_eq!(vector.swap_remove(0), 1);
-    /// assert_eq!(&vector, [4, 2].as_ref());
-    ///
-    /// assert_eq!(vector.swap_remove(1), 2);
-    /// assert_eq!(&vector, [4].as_ref());
+    /// vector.retain_present_in(&[2, 4, 6]);
     ///
-    /// assert_eq!(vector.swap_remove(0), 4);
-    /// assert_eq!(vector.len(), 0);
+    /// assert_eq!(&vector, [2, 4].as_ref());
     /// ```
-    pub fn swap_remove(&mut self, index: usize) -> T {
-        match self.try_swap_remove(index) {
-            Some(item) => item,
-            None => out_of_bounds!("swap_remove", index, self.len()),
-        }
+    #[inline]
+    pub fn retain_present_in(&mut self, other: &[T])
+    where
+        T: PartialEq,
+    {
+        self.retain(|item| other.contains(item));
     }
 
-    /// If the index is in bounds, remove the value contained at `index`
-    /// and return it without conserving order.
-    ///
-    /// The removed value is replaced by the last value making this an
-    /// `O(1)` operation.
+    /// Only keep the items which aren't present in `other`, preserving
+    /// their order (see [`ArrayVec::retain_present_in()`]).
     ///
     /// # Examples
     ///
     /// ```rust
     /// use const_arrayvec::ArrayVec;
-    /// let mut vector = ArrayVec::from([1, 2, 4]);
+    /// let mut vector = ArrayVec::from([1, 2, 3, 4]);
     ///
-    /// assert_eq!(vector.try_swap_remove(0), Some(1));
-    /// assert_eq!(&vector, [4, 2].as_ref());
+    /// vector.retain_absent_from(&[2, 4, 6]);
     ///
-    /// assert_eq!(vector.try_swap_remove(24), None);
-    /// assert_eq!(&vector, [4, 2].as_ref());
+    /// assert_eq!(&vector, [1, 3].as_ref());
     /// ```
     #[inline]
-    pub fn try_swap_remove(&mut self, index: usize) -> Option<T> {
-        if index < self.len() {
-            Some(unsafe { self.swap_remove_unchecked(index) })
-        } else {
-            None
-        }
+    pub fn retain_absent_from(&mut self, other: &[T])
+    where
+        T: PartialEq,
+    {
+        self.retain(|item| !other.contains(item));
     }
 
-    /// Remove the value contained at `index` and return it without
-    /// conserving order.
+    /// Remove every item which is equal to `T::default()`, preserving the
+    /// order of the rest.
     ///
-    /// The removed value is replaced by the last value making this an
-    /// `O(1)` operation.
+    /// # Examples
     ///
-    /// # Safety
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([0_i32, 1, 0, 2, 0]);
     ///
-    /// The index must be in bounds.
-    pub unsafe fn swap_remove_unchecked(&mut self, index: usize) -> T {
-        let new_len = self.len() - 1;
-        let ptr_vec_start = self.as_mut_ptr();
-        let ptr_index = ptr_vec_start.add(index);
-
-        // Read the item from its pointer.
-        let item = ptr::read(ptr_index);
-        // Read the last item from its pointer.
-        let last_item = ptr::read(ptr_vec_start.add(new_len));
-        // Replace the item at `index` with the last item without calling
-        // `drop`.
-        ptr::write(ptr_index, last_item);
-        // Resize the vector so that the last item gets ignored.
-        self.set_len(new_len);
-
-        item
+    /// vector.remove_defaults();
+    ///
+    /// assert_eq!(&vector, [1, 2].as_ref());
+    /// ```
+    pub fn remove_defaults(&mut self)
+    where
+        T: Default + PartialEq,
+    {
+        let default = T::default();
+        self.retain(|item| *item != default);
     }
 
+    /// Remove consecutive repeated items.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 1, 2, 3, 3, 1]);
+    ///
+    /// vector.dedup();
+    ///
+    /// assert_eq!(&vector, [1, 2, 3, 1].as_ref());
+    /// ```
     #[inline]
-    pub fn as_slice(&self) -> &[T] { self.deref() }
-
-    #[inline]
-    pub fn as_slice_mut(&mut self) -> &mut [T] { self.deref_mut() }
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_changed();
+    }
 
-    pub fn try_extend_from_slice(
-        &mut self,
-        other: &[T],
-    ) -> Result<(), CapacityError<()>>
+    /// Remove consecutive repeated items (see [`ArrayVec::dedup()`]),
+    /// returning `true` if anything was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut duplicates = ArrayVec::from([1, 1, 2]);
+    /// assert!(duplicates.dedup_changed());
+    /// assert_eq!(&duplicates, [1, 2].as_ref());
+    ///
+    /// let mut unique = ArrayVec::from([1, 2, 3]);
+    /// assert!(!unique.dedup_changed());
+    /// assert_eq!(&unique, [1, 2, 3].as_ref());
+    /// ```
+    pub fn dedup_changed(&mut self) -> bool
     where
-        T: Copy,
+        T: PartialEq,
     {
-        if self.remaining_capacity() < other.len() {
-            return Err(CapacityError(()));
+        let len = self.len();
+        if len <= 1 {
+            return false;
         }
 
-        let self_len = self.len();
-        let other_len = other.len();
+        // the number of unique items we've seen so far
+        let mut unique = 1;
 
-        unsafe {
-            let dst = self.as_mut_ptr().add(self_len);
-            // Note: we have a mutable reference to self, so it's not possible
-            // for the two arrays to overlap
-            ptr::copy_nonoverlapping(other.as_ptr(), dst, other_len);
-            self.set_len(self_len + other_len);
+        {
+            let items = self.as_slice_mut();
+
+            for i in 1..len {
+                if items[i] != items[unique - 1] {
+                    // move the item back to join the other unique ones
+                    items.swap(i, unique);
+                    unique += 1;
+                }
+            }
+        }
+
+        // all the duplicates are now at the end
+        self.truncate(unique);
+
+        unique != len
+    }
+
+    /// Remove every item which is equal to an earlier one, so each distinct
+    /// item only appears once.
+    ///
+    /// Unlike [`ArrayVec::dedup()`], duplicates don't need to be next to each
+    /// other. Every item is compared against the unique items seen so far, so
+    /// this takes `O(n²)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 1, 3, 2]);
+    ///
+    /// vector.dedup_all();
+    ///
+    /// assert_eq!(&vector, [1, 2, 3].as_ref());
+    /// ```
+    pub fn dedup_all(&mut self)
+    where
+        T: PartialEq,
+    {
+        let len = self.len();
+
+        // the number of unique items we've seen so far
+        let mut unique = 0;
+
+        {
+            let items = self.as_slice_mut();
+
+            for i in 0..len {
+                let (seen, rest) = items.split_at(unique);
+
+                if !seen.contains(&rest[i - unique]) {
+                    // move the item back to join the other unique ones
+                    items.swap(i, unique);
+                    unique += 1;
+                }
+            }
+        }
+
+        // all the duplicates are now at the end
+        self.truncate(unique);
+    }
+
+    /// Insert an item.
+    ///
+    /// # Panics
+    ///
+    /// The vector must have enough space for the item (see
+    /// [`ArrayVec::remaining_capacity()`]).
+    pub fn insert(&mut self, index: usize, item: T) {
+        match self.try_insert(index, item) {
+            Ok(_) => {},
+            Err(e) => panic!("Insert failed: {}", e),
+        }
+    }
+
+    /// Try to insert an item into the vector.
+    ///
+    /// # Examples
+    ///
+    /// The "happy path" works just as expected:
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<u32, 5> = ArrayVec::new();
+    /// vector.push(12);
+    /// vector.push(34);
+    ///
+    /// vector.try_insert(1, 56).unwrap();
+    ///
+    /// assert_eq!(vector.as_slice(), &[12, 56, 34]);
+    /// ```
+    ///
+    /// Trying to insert an item when the [`ArrayVec`] is full will fail,
+    /// returning the original item.
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector = ArrayVec::from([1, 2, 3]);
+    /// println!("{}, {}", vector.len(), vector.capacity());
+    /// println!("{:?}", vector);
+    /// assert!(vector.is_full());
+    ///
+    /// let got = vector.try_insert(1, 7);
+    ///
+    /// assert_eq!(got, Err(CapacityError(7)));
+    /// ```
+    pub fn try_insert(
+        &mut self,
+        index: usize,
+        item: T,
+    ) -> Result<(), CapacityError<T>> {
+        let len = self.len();
+
+        // bounds checks
+        if index > len {
+            out_of_bounds!("try_insert", index, len);
+        }
+        if self.is_full() {
+            return Err(CapacityError(item));
+        }
+
+        unsafe {
+            self.insert_unchecked(index, item);
+        }
+
+        Ok(())
+    }
+
+    /// Try to insert `count` copies of an item into the vector at `index`,
+    /// shifting everything after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// The index must not be greater than the vector's length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector: ArrayVec<u32, 8> = ArrayVec::new();
+    /// vector.push(1);
+    /// vector.push(2);
+    ///
+    /// vector.try_insert_many(1, 3, 0).unwrap();
+    /// assert_eq!(vector.as_slice(), &[1, 0, 0, 0, 2]);
+    ///
+    /// // there's only room for 3 more items
+    /// assert_eq!(vector.try_insert_many(0, 4, 7), Err(CapacityError(7)));
+    /// ```
+    pub fn try_insert_many(
+        &mut self,
+        index: usize,
+        count: usize,
+        item: T,
+    ) -> Result<(), CapacityError<T>>
+    where
+        T: Clone,
+    {
+        let len = self.len();
+
+        // bounds checks
+        if index > len {
+            out_of_bounds!("try_insert_many", index, len);
+        }
+        if self.remaining_capacity() < count {
+            return Err(CapacityError(item));
+        }
+        if count == 0 {
+            return Ok(());
+        }
+
+        unsafe {
+            let start = self.as_mut_ptr().add(index);
+
+            // Shift the tail over to make space. If a clone() panics part-way
+            // through we'll leak the tail instead of double-dropping it.
+            self.set_len(index);
+            ptr::copy(start, start.add(count), len - index);
+
+            for i in 0..count - 1 {
+                start.add(i).write(item.clone());
+            }
+            // the last spot can take the original item
+            start.add(count - 1).write(item);
+
+            self.set_len(len + count);
         }
+
         Ok(())
     }
 
-    #[inline]
-    pub fn drain(&mut self, range: Range<usize>) -> Drain<'_, T, { N }> {
-        Drain::with_range(self, range)
+    /// Insert an item into the vector, removing and returning its last
+    /// item if already full.
+    ///
+    /// # Panics
+    ///
+    /// The item cannot be inserted at an index greater than the
+    /// vector's length or greater or equal to the maximum capacity `N`.
+    ///
+    /// # Examples
+    ///
+    /// When the vector's not full, [`ArrayVec::force_insert`] acts like
+    /// [`ArrayVec::insert`]:
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<u8, 5> = ArrayVec::new();
+    ///
+    /// assert_eq!(vector.force_insert(0, 42), None);
+    /// assert_eq!(vector.force_insert(0, 24), None);
+    /// assert_eq!(&vector, [24, 42].as_ref());
+    /// ```
+    ///
+    /// But when the vector's full, we get a different result:
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([
+    ///     "He".to_owned(),
+    ///     "ya".to_owned(),
+    /// ]);
+    ///
+    /// let out = vector
+    ///     .force_insert(1, "llo".to_owned())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&out, "ya");
+    /// assert_eq!(&vector, ["He".to_owned(), "llo".to_owned()].as_ref());
+    /// ```
+    pub fn force_insert(&mut self, index: usize, item: T) -> Option<T> {
+        let len = self.len();
+
+        let result;
+
+        if index > len || index == N {
+            // Failed bound checks.
+            out_of_bounds!("force_insert", index, len);
+        } else if self.is_full() {
+            // The last item must be removed to perform the insertion.
+
+            unsafe {
+                // Store the last item before it's removed.
+                result = Some(ptr::read(self.as_ptr().add(len - 1)));
+
+                // The last element should be removed so we shouldn't
+                // copy it.
+                self.insert_unchecked_keep_len(index, item, len - 1);
+            }
+        } else {
+            // Since nothing's going to be removed, the vector's size
+            // is going to be increased and nothing will be returned.
+            unsafe {
+                self.insert_unchecked(index, item);
+            }
+            result = None;
+        }
+
+        result
+    }
+
+    /// Push an item onto the end of the vector, removing and returning the
+    /// first (oldest) item if the vector is already full.
+    ///
+    /// The remaining items are shifted along to preserve their order, making
+    /// this `O(n)` when full. See [`ArrayVec::push_or_evict_newest()`] for an
+    /// `O(1)` alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// assert_eq!(vector.push_or_evict_oldest(4), Some(1));
+    /// assert_eq!(&vector, [2, 3, 4].as_ref());
+    /// ```
+    pub fn push_or_evict_oldest(&mut self, item: T) -> Option<T> {
+        if N == 0 {
+            // there's no room, so the new item is also the oldest
+            return Some(item);
+        }
+
+        let evicted = if self.is_full() {
+            Some(self.remove(0))
+        } else {
+            None
+        };
+
+        unsafe {
+            // there's always room now
+            self.push_unchecked(item);
+        }
+
+        evicted
+    }
+
+    /// Push an item onto the end of the vector, dropping the first (oldest)
+    /// item if the vector is already full.
+    ///
+    /// This turns the vector into a fixed-size sliding window. Use
+    /// [`ArrayVec::push_or_evict_oldest()`] if you need the item which fell
+    /// out of the window.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut window = ArrayVec::from([1, 2, 3]);
+    ///
+    /// window.slide(4);
+    ///
+    /// assert_eq!(&window, [2, 3, 4].as_ref());
+    /// ```
+    #[inline]
+    pub fn slide(&mut self, item: T) { self.push_or_evict_oldest(item); }
+
+    /// Push every item in an array onto the end of the vector, removing
+    /// items from the front to make room when necessary.
+    ///
+    /// The removed items are returned in their original order. If the array
+    /// is longer than the vector's capacity, its first few items will be
+    /// removed too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// let evicted = vector.push_all_rotating([4, 5]);
+    ///
+    /// assert_eq!(&vector, [3, 4, 5].as_ref());
+    /// assert_eq!(&evicted, [1, 2].as_ref());
+    /// ```
+    pub fn push_all_rotating<const M: usize>(
+        &mut self,
+        items: [T; M],
+    ) -> ArrayVec<T, { M }> {
+        let mut evicted = ArrayVec::new();
+
+        ArrayVec::from(items).drain_each(|item| {
+            if let Some(oldest) = self.push_or_evict_oldest(item) {
+                // Note: at most len + M - N items get evicted, which is never
+                // more than M
+                evicted.push(oldest);
+            }
+        });
+
+        evicted
+    }
+
+    /// Push an item onto the end of the vector, replacing and returning the
+    /// last (newest) item if the vector is already full.
+    ///
+    /// This is always `O(1)`, but when the vector is full the previous
+    /// newest item is lost instead of the oldest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// assert_eq!(vector.push_or_evict_newest(4), Some(3));
+    /// assert_eq!(&vector, [1, 2, 4].as_ref());
+    /// ```
+    pub fn push_or_evict_newest(&mut self, item: T) -> Option<T> {
+        if N == 0 {
+            return Some(item);
+        }
+
+        if self.is_full() {
+            Some(self.replace(N - 1, item))
+        } else {
+            unsafe {
+                self.push_unchecked(item);
+            }
+            None
+        }
+    }
+
+    /// Use the vector to collect the `N` largest items seen so far.
+    ///
+    /// If the vector isn't full, `item` is simply pushed. Otherwise it
+    /// replaces the smallest item when it is larger than that item, with
+    /// whichever of the two didn't make the cut being returned.
+    ///
+    /// The items aren't kept in any particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut top: ArrayVec<u32, 2> = ArrayVec::new();
+    ///
+    /// for &item in &[5, 1, 3, 2, 4] {
+    ///     top.push_top_k(item);
+    /// }
+    ///
+    /// top.sort();
+    /// assert_eq!(&top, [4, 5].as_ref());
+    /// ```
+    pub fn push_top_k(&mut self, item: T) -> Option<T>
+    where
+        T: Ord,
+    {
+        if !self.is_full() {
+            unsafe {
+                self.push_unchecked(item);
+            }
+            return None;
+        }
+
+        let smallest = self
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(index, _)| index);
+
+        match smallest {
+            Some(index) if item > self[index] => {
+                Some(self.replace(index, item))
+            },
+            // either the item is too small or we have no capacity
+            _ => Some(item),
+        }
+    }
+
+    /// Insert an item into the vector without checking if the index is
+    /// valid or if the vector isn't full.
+    ///
+    /// # Safety
+    ///
+    /// If you plan on using this function, you need to check for the
+    /// 2 previously mentioned conditions yourself before calling this
+    /// method.
+    #[inline]
+    pub unsafe fn insert_unchecked(&mut self, index: usize, item: T) {
+        let len = self.len();
+        self.insert_unchecked_keep_len(index, item, len);
+        self.set_len(len + 1);
+    }
+
+    /// Insert an item into the vector without checking if the index is
+    /// valid or if the vector isn't full or the vector's length and
+    /// without incrementing the vector's length.
+    ///
+    /// # Safety
+    ///
+    /// If you plan on using this function, you need to check for the
+    /// 3 previously mentioned conditions yourself before calling this
+    /// method. You also need to increment the vector's length afterward
+    /// yourself.
+    unsafe fn insert_unchecked_keep_len(
+        &mut self,
+        index: usize,
+        item: T,
+        len: usize,
+    ) {
+        // The spot to put the new value at.
+        let ptr_index = self.as_mut_ptr().add(index);
+        // Shift everything over to make space. (Duplicating the
+        // `index`th element into two consecutive places.)
+        ptr::copy(ptr_index, ptr_index.add(1), len - index);
+        // Write it in, overwriting the first copy of the `index`th
+        // element.
+        ptr::write(ptr_index, item);
+    }
+
+    /// Remove the value contained at `index` and return it.
+    ///
+    /// # Panics
+    ///
+    /// The index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([4, 3, 2]);
+    ///
+    /// let three = vector.remove(1);
+    ///
+    /// assert_eq!(three, 3);
+    /// assert_eq!(&vector, [4, 2].as_ref());
+    /// ```
+    pub fn remove(&mut self, index: usize) -> T {
+        match self.try_remove(index) {
+            Some(item) => item,
+            None => out_of_bounds!("remove", index, self.len()),
+        }
+    }
+
+    /// If `index` is in bounds, remove the value contained at `index`
+    /// and return it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([4, 3, 2]);
+    ///
+    /// let three = vector.try_remove(1);
+    /// let what = vector.try_remove(24);
+    ///
+    /// assert_eq!(three, Some(3));
+    /// assert_eq!(what, None);
+    /// assert_eq!(&vector, [4, 2].as_ref());
+    /// ```
+    #[inline]
+    pub fn try_remove(&mut self, index: usize) -> Option<T> {
+        if index < self.len() {
+            Some(unsafe { self.remove_unchecked(index) })
+        } else {
+            None
+        }
+    }
+
+    /// Remove the value contained at `index` and return it.
+    ///
+    /// # Safety
+    ///
+    /// The index must be in bounds.
+    pub unsafe fn remove_unchecked(&mut self, index: usize) -> T {
+        let len = self.len();
+
+        // Where the value to remove is.
+        let ptr_index = self.as_mut_ptr().add(index);
+        // Read the value before sending it to the other world.
+        let item = ptr::read(ptr_index);
+        // Shift every value after the removed one to the left.
+        ptr::copy(ptr_index.add(1), ptr_index, len - index - 1);
+        // We removed an item, so the length should be decremented.
+        self.set_len(len - 1);
+
+        item
+    }
+
+    /// Remove the value contained at `index` and return it without
+    /// conserving order.
+    ///
+    /// The removed value is replaced by the last value making this an
+    /// `O(1)` operation.
+    ///
+    /// # Panics
+    ///
+    /// The index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 4]);
+    ///
+    /// assert_eq!(vector.swap_remove(0), 1);
+    /// assert_eq!(&vector, [4, 2].as_ref());
+    ///
+    /// assert_eq!(vector.swap_remove(1), 2);
+    /// assert_eq!(&vector, [4].as_ref());
+    ///
+    /// assert_eq!(vector.swap_remove(0), 4);
+    /// assert_eq!(vector.len(), 0);
+    /// ```
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        match self.try_swap_remove(index) {
+            Some(item) => item,
+            None => out_of_bounds!("swap_remove", index, self.len()),
+        }
+    }
+
+    /// If the index is in bounds, remove the value contained at `index`
+    /// and return it without conserving order.
+    ///
+    /// The removed value is replaced by the last value making this an
+    /// `O(1)` operation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 4]);
+    ///
+    /// assert_eq!(vector.try_swap_remove(0), Some(1));
+    /// assert_eq!(&vector, [4, 2].as_ref());
+    ///
+    /// assert_eq!(vector.try_swap_remove(24), None);
+    /// assert_eq!(&vector, [4, 2].as_ref());
+    /// ```
+    #[inline]
+    pub fn try_swap_remove(&mut self, index: usize) -> Option<T> {
+        if index < self.len() {
+            Some(unsafe { self.swap_remove_unchecked(index) })
+        } else {
+            None
+        }
+    }
+
+    /// Remove the value contained at `index` and return it without
+    /// conserving order.
+    ///
+    /// The removed value is replaced by the last value making this an
+    /// `O(1)` operation.
+    ///
+    /// # Safety
+    ///
+    /// The index must be in bounds.
+    pub unsafe fn swap_remove_unchecked(&mut self, index: usize) -> T {
+        let new_len = self.len() - 1;
+        let ptr_vec_start = self.as_mut_ptr();
+        let ptr_index = ptr_vec_start.add(index);
+
+        // Read the item from its pointer.
+        let item = ptr::read(ptr_index);
+        // Read the last item from its pointer.
+        let last_item = ptr::read(ptr_vec_start.add(new_len));
+        // Replace the item at `index` with the last item without calling
+        // `drop`.
+        ptr::write(ptr_index, last_item);
+        // Resize the vector so that the last item gets ignored.
+        self.set_len(new_len);
+
+        item
+    }
+
+    /// Replace the item at `index`, returning the original.
+    ///
+    /// # Panics
+    ///
+    /// The index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// assert_eq!(vector.replace(1, 42), 2);
+    /// assert_eq!(&vector, [1, 42, 3].as_ref());
+    /// ```
+    pub fn replace(&mut self, index: usize, item: T) -> T {
+        let len = self.len();
+
+        match self.try_replace(index, item) {
+            Some(original) => original,
+            None => out_of_bounds!("replace", index, len),
+        }
+    }
+
+    /// If `index` is in bounds, replace the item at `index` and return the
+    /// original. Otherwise `item` is dropped and `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// assert_eq!(vector.try_replace(1, 42), Some(2));
+    /// assert_eq!(vector.try_replace(24, 7), None);
+    /// assert_eq!(&vector, [1, 42, 3].as_ref());
+    /// ```
+    #[inline]
+    pub fn try_replace(&mut self, index: usize, item: T) -> Option<T> {
+        self.get_mut(index).map(|slot| mem::replace(slot, item))
+    }
+
+    /// Get the item at `index`, or `default` if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// assert_eq!(vector.get_or(1, &0), &2);
+    /// assert_eq!(vector.get_or(10, &0), &0);
+    /// ```
+    #[inline]
+    pub fn get_or<'a>(&'a self, index: usize, default: &'a T) -> &'a T {
+        self.get(index).unwrap_or(default)
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] { self.deref() }
+
+    #[inline]
+    pub fn as_slice_mut(&mut self) -> &mut [T] { self.deref_mut() }
+
+    /// Iterate over each item along with its index.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([10, 20, 30]);
+    ///
+    /// let pairs: Vec<(usize, &u32)> = vector.enumerate().collect();
+    ///
+    /// assert_eq!(pairs, vec![(0, &10), (1, &20), (2, &30)]);
+    /// ```
+    #[inline]
+    pub fn enumerate(&self) -> Enumerate<slice::Iter<'_, T>> {
+        self.iter().enumerate()
+    }
+
+    /// Get the vector's items along with its remaining capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<u32, 4> = ArrayVec::new();
+    /// vector.push(1);
+    /// vector.push(2);
+    ///
+    /// let (items, spare) = vector.parts();
+    ///
+    /// assert_eq!(items, &[1, 2]);
+    /// assert_eq!(spare, 2);
+    /// ```
+    #[inline]
+    pub fn parts(&self) -> (&[T], usize) {
+        (self.as_slice(), self.remaining_capacity())
+    }
+
+    /// The mutable version of [`ArrayVec::parts()`].
+    #[inline]
+    pub fn parts_mut(&mut self) -> (&mut [T], usize) {
+        let spare = self.remaining_capacity();
+        (self.as_slice_mut(), spare)
+    }
+
+    /// Split the vector into two mutable slices at `mid`.
+    ///
+    /// # Panics
+    ///
+    /// `mid` is greater than the vector's length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// let (left, right) = vector.split_at_mut(2);
+    /// left[0] = 10;
+    /// right[1] = 40;
+    ///
+    /// assert_eq!(&vector, [10, 2, 3, 40].as_ref());
+    /// ```
+    pub fn split_at_mut(&mut self, mid: usize) -> (&mut [T], &mut [T]) {
+        let len = self.len();
+
+        if mid > len {
+            out_of_bounds!("split_at_mut", mid, len);
+        }
+
+        self.as_slice_mut().split_at_mut(mid)
+    }
+
+    pub fn try_extend_from_slice(
+        &mut self,
+        other: &[T],
+    ) -> Result<(), CapacityError<()>>
+    where
+        T: Copy,
+    {
+        if self.remaining_capacity() < other.len() {
+            return Err(CapacityError(()));
+        }
+
+        let self_len = self.len();
+        let other_len = other.len();
+
+        unsafe {
+            let dst = self.as_mut_ptr().add(self_len);
+            // Note: we have a mutable reference to self, so it's not possible
+            // for the two arrays to overlap
+            ptr::copy_nonoverlapping(other.as_ptr(), dst, other_len);
+            self.set_len(self_len + other_len);
+        }
+        Ok(())
+    }
+
+    /// Append several slices, one after the other.
+    ///
+    /// The combined length is checked before anything is copied, so if they
+    /// won't all fit the vector is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector: ArrayVec<u32, 8> = ArrayVec::new();
+    ///
+    /// vector.try_extend_from_slices(&[&[1, 2], &[3], &[4, 5]]).unwrap();
+    /// assert_eq!(&vector, [1, 2, 3, 4, 5].as_ref());
+    ///
+    /// let overflow = vector.try_extend_from_slices(&[&[6, 7], &[8, 9]]);
+    /// assert_eq!(overflow, Err(CapacityError(())));
+    /// assert_eq!(&vector, [1, 2, 3, 4, 5].as_ref());
+    /// ```
+    pub fn try_extend_from_slices(
+        &mut self,
+        slices: &[&[T]],
+    ) -> Result<(), CapacityError<()>>
+    where
+        T: Copy,
+    {
+        let mut total: usize = 0;
+
+        for slice in slices {
+            match total.checked_add(slice.len()) {
+                Some(sum) if sum <= self.remaining_capacity() => total = sum,
+                _ => return Err(CapacityError(())),
+            }
+        }
+
+        for slice in slices {
+            // Note: we've already checked the total length will fit
+            self.try_extend_from_slice(slice)?;
+        }
+
+        Ok(())
+    }
+
+    /// Append as much of `other` as will fit, returning the number of items
+    /// which were added.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<u32, 4> = ArrayVec::new();
+    /// vector.try_extend_from_slice(&[1, 2]).unwrap();
+    ///
+    /// let added = vector.push_clamped_slice(&[3, 4, 5, 6, 7]);
+    ///
+    /// assert_eq!(added, 2);
+    /// assert_eq!(&vector, [1, 2, 3, 4].as_ref());
+    /// ```
+    pub fn push_clamped_slice(&mut self, other: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let count = other.len().min(self.remaining_capacity());
+
+        // Note: we just made sure this will fit
+        let _ = self.try_extend_from_slice(&other[..count]);
+
+        count
+    }
+
+    /// Append items taken alternately from `a` and `b`, followed by whatever
+    /// is left of the longer slice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector: ArrayVec<u32, 5> = ArrayVec::new();
+    ///
+    /// vector.try_extend_interleaved(&[1, 3, 5], &[2, 4]).unwrap();
+    /// assert_eq!(&vector, [1, 2, 3, 4, 5].as_ref());
+    ///
+    /// let got = vector.try_extend_interleaved(&[6], &[7]);
+    /// assert_eq!(got, Err(CapacityError(())));
+    ///
+    /// // slices of zero-sized types can be long enough to overflow a usize
+    /// let mut units: ArrayVec<(), 4> = ArrayVec::new();
+    /// let got = units.try_extend_interleaved(&[(); usize::MAX], &[(), ()]);
+    /// assert_eq!(got, Err(CapacityError(())));
+    /// assert!(units.is_empty());
+    /// ```
+    pub fn try_extend_interleaved(
+        &mut self,
+        a: &[T],
+        b: &[T],
+    ) -> Result<(), CapacityError<()>>
+    where
+        T: Copy,
+    {
+        match a.len().checked_add(b.len()) {
+            Some(total) if total <= self.remaining_capacity() => {},
+            _ => return Err(CapacityError(())),
+        }
+
+        for i in 0..a.len().max(b.len()) {
+            let items = a.get(i).into_iter().chain(b.get(i));
+
+            for &item in items {
+                unsafe {
+                    // we've already checked everything will fit
+                    self.push_unchecked(item);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Clone each item in `other` onto the end of the vector.
+    ///
+    /// This is the [`Clone`] version of [`ArrayVec::try_extend_from_slice()`].
+    /// If a `clone()` panics, any items which have already been cloned are
+    /// dropped and the vector is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector: ArrayVec<String, 3> = ArrayVec::new();
+    /// let words = ["Hello".to_owned(), "World".to_owned()];
+    ///
+    /// vector.try_clone_append(&words).unwrap();
+    /// assert_eq!(&vector, words.as_ref());
+    ///
+    /// assert_eq!(vector.try_clone_append(&words), Err(CapacityError(())));
+    /// assert_eq!(vector.len(), 2);
+    /// ```
+    pub fn try_clone_append(
+        &mut self,
+        other: &[T],
+    ) -> Result<(), CapacityError<()>>
+    where
+        T: Clone,
+    {
+        if self.remaining_capacity() < other.len() {
+            return Err(CapacityError(()));
+        }
+
+        let original_length = self.len();
+        let guard = TruncateOnDrop {
+            vector: self,
+            length: original_length,
+        };
+
+        for item in other {
+            unsafe {
+                // we've already checked there's enough room
+                guard.vector.push_unchecked(item.clone());
+            }
+        }
+
+        // everything was cloned successfully, don't roll back
+        mem::forget(guard);
+
+        Ok(())
+    }
+
+    /// Move every item from an iterator into the vector, leaving the vector
+    /// untouched if they won't all fit.
+    ///
+    /// When the iterator yields more items than there is room for, the items
+    /// which were already appended are moved into a new [`ArrayVec`] and
+    /// handed back alongside the error. The item which didn't fit is dropped.
+    ///
+    /// If the iterator panics, any appended items are dropped and the vector
+    /// is restored to its original length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector: ArrayVec<u32, 4> = ArrayVec::new();
+    /// vector.push(1);
+    ///
+    /// vector.try_extend_atomic(vec![2, 3]).unwrap();
+    /// assert_eq!(&vector, [1, 2, 3].as_ref());
+    ///
+    /// let (err, pulled) =
+    ///     vector.try_extend_atomic(vec![4, 5, 6]).unwrap_err();
+    /// assert_eq!(err, CapacityError(()));
+    /// assert_eq!(&pulled, [4].as_ref());
+    /// assert_eq!(&vector, [1, 2, 3].as_ref());
+    /// ```
+    pub fn try_extend_atomic<I>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), (CapacityError<()>, ArrayVec<T, { N }>)>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let original_length = self.len();
+        let guard = TruncateOnDrop {
+            vector: self,
+            length: original_length,
+        };
+
+        for item in iter {
+            if let Err(CapacityError(overflow)) = guard.vector.try_push(item) {
+                let pulled = guard.vector.truncate_returning(original_length);
+                // the vector is back to its original length, so there's
+                // nothing left for the guard to roll back
+                mem::forget(guard);
+                mem::drop(overflow);
+
+                return Err((CapacityError(()), pulled));
+            }
+        }
+
+        // everything fit, don't roll back
+        mem::forget(guard);
+
+        Ok(())
+    }
+
+    /// Clone each item from another [`ArrayVec`] (possibly with a different
+    /// capacity) onto the end of this one.
+    ///
+    /// See [`ArrayVec::try_clone_append()`] for what happens if a `clone()`
+    /// panics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector: ArrayVec<String, 4> = ArrayVec::new();
+    /// vector.push("Hello".to_owned());
+    /// let mut other: ArrayVec<String, 2> = ArrayVec::new();
+    /// other.push("World".to_owned());
+    /// other.push("!".to_owned());
+    ///
+    /// vector.try_extend_from_arrayvec(&other).unwrap();
+    /// assert_eq!(vector.len(), 3);
+    /// assert_eq!(vector[2], "!");
+    ///
+    /// assert_eq!(
+    ///     vector.try_extend_from_arrayvec(&other),
+    ///     Err(CapacityError(()))
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_extend_from_arrayvec<const M: usize>(
+        &mut self,
+        other: &ArrayVec<T, { M }>,
+    ) -> Result<(), CapacityError<()>>
+    where
+        T: Clone,
+    {
+        self.try_clone_append(other.as_slice())
+    }
+
+    /// Push default values onto the end of the vector until it is full.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<i32, 4> = ArrayVec::new();
+    /// vector.push(1);
+    /// vector.push(2);
+    ///
+    /// vector.pad_to_full();
+    ///
+    /// assert_eq!(&vector, [1, 2, 0, 0].as_ref());
+    /// ```
+    #[inline]
+    pub fn pad_to_full(&mut self)
+    where
+        T: Default,
+    {
+        self.pad_to(N);
+    }
+
+    /// Push default values onto the end of the vector until it contains
+    /// `length` items. Nothing happens if it is already that long.
+    ///
+    /// # Panics
+    ///
+    /// The `length` can't be more than the vector's capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<i32, 4> = ArrayVec::new();
+    /// vector.push(1);
+    ///
+    /// vector.pad_to(3);
+    ///
+    /// assert_eq!(&vector, [1, 0, 0].as_ref());
+    /// ```
+    pub fn pad_to(&mut self, length: usize)
+    where
+        T: Default,
+    {
+        if length > N {
+            capacity_exceeded!("pad_to", length, N);
+        }
+
+        while self.len() < length {
+            unsafe {
+                // we've already made sure it'll fit
+                self.push_unchecked(T::default());
+            }
+        }
+    }
+
+    /// Drop the first `count` items and shift the rest to the front, filling
+    /// the gap at the end with clones of `fill` so the length stays the same.
+    ///
+    /// If `count` is greater than the length, every item is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut display = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// display.shift_left(2, 0);
+    ///
+    /// assert_eq!(&display, [3, 4, 0, 0].as_ref());
+    /// ```
+    pub fn shift_left(&mut self, count: usize, fill: T)
+    where
+        T: Clone,
+    {
+        let len = self.len();
+        let count = count.min(len);
+
+        // move the doomed items to the end so truncate() can drop them
+        self.rotate_left(count);
+        self.truncate(len - count);
+
+        for _ in 0..count {
+            unsafe {
+                // we're only putting back as many items as we removed
+                self.push_unchecked(fill.clone());
+            }
+        }
+    }
+
+    /// Calculate the dot product of this vector and `other`.
+    ///
+    /// If the lengths differ, the extra items in the longer one are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// assert_eq!(vector.dot(&[4, 5, 6]), 32);
+    /// ```
+    pub fn dot(&self, other: &[T]) -> T
+    where
+        T: Copy + Mul<Output = T> + Sum,
+    {
+        self.iter().zip(other).map(|(&a, &b)| a * b).sum()
+    }
+
+    /// Get a new vector where each item is the largest of the corresponding
+    /// item and every item before it, leaving the original untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 3, 2, 5, 4]);
+    ///
+    /// let maxima = vector.running_max();
+    ///
+    /// assert_eq!(&maxima, [1, 3, 3, 5, 5].as_ref());
+    /// assert_eq!(&vector, [1, 3, 2, 5, 4].as_ref());
+    /// ```
+    pub fn running_max(&self) -> ArrayVec<T, { N }>
+    where
+        T: Copy + Ord,
+    {
+        let mut maxima = ArrayVec::new();
+
+        for &item in self.iter() {
+            let max = match maxima.last() {
+                Some(&previous) => cmp::max(previous, item),
+                None => item,
+            };
+
+            unsafe {
+                // we'll never have more items than the original vector
+                maxima.push_unchecked(max);
+            }
+        }
+
+        maxima
+    }
+
+    /// Replace each item with the sum of itself and every item before it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// vector.prefix_sum();
+    ///
+    /// assert_eq!(&vector, [1, 3, 6, 10].as_ref());
+    /// ```
+    pub fn prefix_sum(&mut self)
+    where
+        T: Copy + AddAssign,
+    {
+        for i in 1..self.len() {
+            let previous = self[i - 1];
+            self[i] += previous;
+        }
+    }
+
+    /// Call a function on each item in the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// vector.apply(|x| *x *= 2);
+    ///
+    /// assert_eq!(&vector, [2, 4, 6].as_ref());
+    /// ```
+    #[inline]
+    pub fn apply<F: FnMut(&mut T)>(&mut self, f: F) {
+        self.iter_mut().for_each(f);
+    }
+
+    /// Call `f` on each item along with a mutable slice of every item after
+    /// it.
+    ///
+    /// This is useful for in-place algorithms which need to look ahead. A
+    /// callback is used instead of an iterator because the slice handed out
+    /// at one step contains the items handed out at later steps.
+    ///
+    /// # Examples
+    ///
+    /// Implementing a selection sort:
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// use core::mem;
+    /// let mut vector = ArrayVec::from([3, 5, 1, 4, 2]);
+    ///
+    /// vector.for_each_with_rest(|item, rest| {
+    ///     if let Some(smallest) = rest.iter_mut().min() {
+    ///         if *smallest < *item {
+    ///             mem::swap(item, smallest);
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(&vector, [1, 2, 3, 4, 5].as_ref());
+    /// ```
+    pub fn for_each_with_rest<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T, &mut [T]),
+    {
+        let mut remaining = self.as_slice_mut();
+
+        while let Some((item, rest)) = remaining.split_first_mut() {
+            f(item, rest);
+            remaining = rest;
+        }
+    }
+
+    /// Restrict every item to the range `min..=max`, replacing anything
+    /// outside it with the nearest bound.
+    ///
+    /// # Panics
+    ///
+    /// `min` must not be greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut signal = ArrayVec::from([-5, 2, 10]);
+    ///
+    /// signal.clamp_each(0, 8);
+    ///
+    /// assert_eq!(&signal, [0, 2, 8].as_ref());
+    /// ```
+    pub fn clamp_each(&mut self, min: T, max: T)
+    where
+        T: Ord + Copy,
+    {
+        assert!(
+            min <= max,
+            "The minimum must not be greater than the maximum"
+        );
+
+        for item in self.iter_mut() {
+            *item = cmp::max(min, cmp::min(*item, max));
+        }
+    }
+
+    /// Replace every item equal to `from` with a clone of `to`, returning the
+    /// number of items replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([2, 1, 2, 3]);
+    ///
+    /// let replaced = vector.replace_all(&2, &9);
+    ///
+    /// assert_eq!(replaced, 2);
+    /// assert_eq!(&vector, [9, 1, 9, 3].as_ref());
+    /// ```
+    pub fn replace_all(&mut self, from: &T, to: &T) -> usize
+    where
+        T: PartialEq + Clone,
+    {
+        let mut replaced = 0;
+
+        for item in self.iter_mut().filter(|item| *item == from) {
+            *item = to.clone();
+            replaced += 1;
+        }
+
+        replaced
+    }
+
+    /// Overwrite every item in the vector with a clone of `item`, without
+    /// changing its length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<u32, 5> = ArrayVec::new();
+    /// vector.try_extend_from_slice(&[1, 2, 3]).unwrap();
+    ///
+    /// vector.set_all(9);
+    ///
+    /// assert_eq!(&vector, [9, 9, 9].as_ref());
+    /// assert_eq!(vector.len(), 3);
+    /// ```
+    pub fn set_all(&mut self, item: T)
+    where
+        T: Clone,
+    {
+        if let Some((last, rest)) = self.split_last_mut() {
+            for slot in rest {
+                *slot = item.clone();
+            }
+
+            // the last slot can take the original
+            *last = item;
+        }
+    }
+
+    /// Push items from an iterator until the vector is full, returning the
+    /// iterator so any remaining items can be put somewhere else.
+    ///
+    /// Items are only taken from the iterator when there is room for them,
+    /// so nothing gets dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<u8, 2> = ArrayVec::new();
+    ///
+    /// let rest = vector.extend_until_full(0..5);
+    ///
+    /// assert_eq!(&vector, [0, 1].as_ref());
+    /// assert_eq!(rest.collect::<Vec<_>>(), vec![2, 3, 4]);
+    /// ```
+    pub fn extend_until_full<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> I::IntoIter {
+        let mut iter = iter.into_iter();
+
+        while !self.is_full() {
+            match iter.next() {
+                // we just checked there's room
+                Some(item) => unsafe { self.push_unchecked(item) },
+                None => break,
+            }
+        }
+
+        iter
+    }
+
+    /// Push the value from every `Some` in an iterator, skipping any `None`s.
+    ///
+    /// If the vector fills up while there are still values left, the
+    /// remaining values are dropped and an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector: ArrayVec<u32, 3> = ArrayVec::new();
+    ///
+    /// vector.try_extend_flatten(vec![Some(1), None, Some(2)]).unwrap();
+    /// assert_eq!(&vector, [1, 2].as_ref());
+    ///
+    /// let got = vector.try_extend_flatten(vec![None, Some(3), Some(4)]);
+    /// assert_eq!(got, Err(CapacityError(())));
+    /// assert_eq!(&vector, [1, 2, 3].as_ref());
+    /// ```
+    pub fn try_extend_flatten<I>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), CapacityError<()>>
+    where
+        I: IntoIterator<Item = Option<T>>,
+    {
+        for item in iter.into_iter().flatten() {
+            self.try_push(item).map_err(|_| CapacityError(()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Give a closure read-only access to the vector's items and write access
+    /// to its spare capacity, letting it append new items in place.
+    ///
+    /// The closure returns a value which is passed back to the caller, and
+    /// the number of leading spare slots it initialized. Those items are then
+    /// added to the vector.
+    ///
+    /// # Safety
+    ///
+    /// The count returned by the closure must be no greater than the number
+    /// of spare slots, and that many leading spare slots must have been
+    /// initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut vector: ArrayVec<u32, 4> = ArrayVec::new();
+    /// vector.try_extend_from_slice(&[1, 2, 3]).unwrap();
+    ///
+    /// let sum = unsafe {
+    ///     vector.with_spare(|items, spare: &mut [MaybeUninit<u32>]| {
+    ///         let sum = items.iter().sum::<u32>();
+    ///         spare[0] = MaybeUninit::new(sum);
+    ///         (sum, 1)
+    ///     })
+    /// };
+    ///
+    /// assert_eq!(sum, 6);
+    /// assert_eq!(&vector, [1, 2, 3, 6].as_ref());
+    /// ```
+    pub unsafe fn with_spare<R, F>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&[T], &mut [MaybeUninit<T>]) -> (R, usize),
+    {
+        let len = self.len();
+        let (items, spare) = self.items.split_at_mut(len);
+        let spare_len = spare.len();
+
+        // Note: the first len items are always initialized
+        let items = slice::from_raw_parts(items.as_ptr() as *const T, len);
+        let (result, added) = f(items, spare);
+
+        debug_assert!(
+            added <= spare_len,
+            "The closure initialized more items than there was room for"
+        );
+        self.set_len(len + added);
+
+        result
+    }
+
+    #[inline]
+    pub fn drain(&mut self, range: Range<usize>) -> Drain<'_, T, { N }> {
+        Drain::with_range(self, range)
+    }
+
+    /// Move as many items as will fit from the front of the vector into
+    /// `dst`, returning the number of items moved.
+    ///
+    /// The items in `dst` which get overwritten are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3, 4]);
+    /// let mut dst = [0; 2];
+    ///
+    /// let moved = vector.drain_into_slice(&mut dst);
+    ///
+    /// assert_eq!(moved, 2);
+    /// assert_eq!(dst, [1, 2]);
+    /// assert_eq!(&vector, [3, 4].as_ref());
+    /// ```
+    pub fn drain_into_slice(&mut self, dst: &mut [T]) -> usize {
+        let count = dst.len().min(self.len());
+
+        for (slot, item) in dst.iter_mut().zip(self.drain(0..count)) {
+            *slot = item;
+        }
+
+        count
+    }
+
+    /// Remove items from the front of the vector `K` at a time, stopping
+    /// when there are fewer than `K` items left.
+    ///
+    /// # Panics
+    ///
+    /// `K` must not be zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3, 4, 5]);
+    ///
+    /// let chunks: Vec<[u32; 2]> = vector.drain_chunks::<2>().collect();
+    ///
+    /// assert_eq!(chunks, vec![[1, 2], [3, 4]]);
+    /// assert_eq!(&vector, [5].as_ref());
+    /// ```
+    #[inline]
+    pub fn drain_chunks<const K: usize>(
+        &mut self,
+    ) -> DrainChunks<'_, T, { N }, { K }> {
+        DrainChunks::new(self)
+    }
+
+    /// Move the items into a heap-allocated slice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// let boxed: Box<[u32]> = vector.to_boxed_slice();
+    ///
+    /// assert_eq!(&*boxed, &[1, 2, 3]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_boxed_slice(mut self) -> alloc::boxed::Box<[T]> {
+        let len = self.len();
+        let mut items = alloc::vec::Vec::with_capacity(len);
+
+        unsafe {
+            // ownership is being transferred to the Vec, so make sure our
+            // destructor won't touch the items
+            self.set_len(0);
+            ptr::copy_nonoverlapping(self.as_ptr(), items.as_mut_ptr(), len);
+            items.set_len(len);
+        }
+
+        items.into_boxed_slice()
+    }
+
+    /// Take the vector's contents, leaving an empty vector in its place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// let taken = vector.take();
+    ///
+    /// assert!(vector.is_empty());
+    /// assert_eq!(&taken, [1, 2, 3].as_ref());
+    /// ```
+    #[inline]
+    pub fn take(&mut self) -> ArrayVec<T, { N }> { mem::take(self) }
+
+    /// Swap the contents (items and length) of this vector with another.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut first = ArrayVec::from([1, 2, 3]);
+    /// let mut second: ArrayVec<u32, 3> = ArrayVec::new();
+    /// second.push(4);
+    ///
+    /// first.swap_contents(&mut second);
+    ///
+    /// assert_eq!(&first, [4].as_ref());
+    /// assert_eq!(&second, [1, 2, 3].as_ref());
+    /// ```
+    #[inline]
+    pub fn swap_contents(&mut self, other: &mut ArrayVec<T, { N }>) {
+        // both vectors have the same capacity, so swapping the backing
+        // buffers and lengths wholesale is all we need to do
+        mem::swap(self, other);
+    }
+
+    /// Rotate the vector's items so the item at `index` becomes the first
+    /// one, preserving their relative order.
+    ///
+    /// # Panics
+    ///
+    /// The index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([0, 1, 2, 3]);
+    ///
+    /// vector.make_front(2);
+    ///
+    /// assert_eq!(&vector, [2, 3, 0, 1].as_ref());
+    /// ```
+    pub fn make_front(&mut self, index: usize) {
+        let len = self.len();
+
+        if index >= len {
+            out_of_bounds!("make_front", index, len);
+        }
+
+        self.as_slice_mut().rotate_left(index);
+    }
+
+    /// Move the item at `from` so it ends up at index `to`, shifting the
+    /// items in between to preserve their order.
+    ///
+    /// # Panics
+    ///
+    /// Either index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from(['a', 'b', 'c', 'd']);
+    ///
+    /// vector.move_element(0, 2);
+    /// assert_eq!(&vector, ['b', 'c', 'a', 'd'].as_ref());
+    ///
+    /// vector.move_element(3, 0);
+    /// assert_eq!(&vector, ['d', 'b', 'c', 'a'].as_ref());
+    /// ```
+    pub fn move_element(&mut self, from: usize, to: usize) {
+        let len = self.len();
+
+        if from >= len {
+            out_of_bounds!("move_element", from, len);
+        }
+        if to >= len {
+            out_of_bounds!("move_element", to, len);
+        }
+
+        if from < to {
+            self[from..=to].rotate_left(1);
+        } else {
+            self[to..=from].rotate_right(1);
+        }
+    }
+
+    /// Get a reference to the largest item, or `None` if the vector is empty.
+    ///
+    /// This isn't called `max()` because it would be shadowed by
+    /// [`Ord::max()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([3, 1, 4, 1, 5]);
+    ///
+    /// assert_eq!(vector.max_item(), Some(&5));
+    /// assert_eq!(vector.min_item(), Some(&1));
+    /// ```
+    #[inline]
+    pub fn max_item(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().max()
+    }
+
+    /// Get a reference to the smallest item, or `None` if the vector is
+    /// empty (see [`ArrayVec::max_item()`]).
+    #[inline]
+    pub fn min_item(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().min()
+    }
+
+    /// Get a reference to the item which gives the largest value from `f()`,
+    /// or `None` if the vector is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([-3_i32, 1, -4]);
+    ///
+    /// assert_eq!(vector.max_by_key(|x| x.abs()), Some(&-4));
+    /// assert_eq!(vector.min_by_key(|x| x.abs()), Some(&1));
+    /// ```
+    #[inline]
+    pub fn max_by_key<K, F>(&self, mut f: F) -> Option<&T>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.iter().max_by_key(|item| f(item))
+    }
+
+    /// Get a reference to the item which gives the smallest value from `f()`,
+    /// or `None` if the vector is empty.
+    #[inline]
+    pub fn min_by_key<K, F>(&self, mut f: F) -> Option<&T>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.iter().min_by_key(|item| f(item))
+    }
+
+    /// Lazily check whether the vector contains the same items as an
+    /// iterator, stopping at the first difference.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// assert!(vector.iter_eq(1..=3));
+    /// assert!(!vector.iter_eq(1..=4));
+    /// assert!(!vector.iter_eq(1..=2));
+    /// ```
+    pub fn iter_eq<I: IntoIterator<Item = T>>(&self, other: I) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut other = other.into_iter();
+
+        for item in self.iter() {
+            match other.next() {
+                Some(ref other_item) if item == other_item => {},
+                _ => return false,
+            }
+        }
+
+        // the iterator must not have any leftover items
+        other.next().is_none()
+    }
+
+    /// Check whether two vectors are the same length and each pair of items
+    /// is considered equal by `eq`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let first = ArrayVec::from([1.0, 2.0]);
+    /// let second = ArrayVec::from([1.001, 2.0]);
+    ///
+    /// let close_enough = |a: &f64, b: &f64| (a - b).abs() < 0.01;
+    ///
+    /// assert!(first.eq_by(&second, close_enough));
+    /// assert!(first != second);
+    /// ```
+    pub fn eq_by<F, const M: usize>(
+        &self,
+        other: &ArrayVec<T, { M }>,
+        mut eq: F,
+    ) -> bool
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        self.len() == other.len()
+            && self.iter().zip(other.iter()).all(|(a, b)| eq(a, b))
+    }
+
+    /// Check whether `other` contains the same items as this vector,
+    /// ignoring their order.
+    ///
+    /// Sorted copies of both are made on the stack, so this takes
+    /// `O(n log n)` time without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// assert!(vector.eq_unordered(&[3, 1, 2]));
+    /// assert!(!vector.eq_unordered(&[1, 2, 2]));
+    /// ```
+    pub fn eq_unordered(&self, other: &[T]) -> bool
+    where
+        T: Ord + Clone,
+    {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        let mut ours = self.clone();
+        let mut theirs: ArrayVec<T, { N }> = ArrayVec::new();
+        // other is the same length as us, so it'll always fit
+        let _ = theirs.try_clone_append(other);
+
+        ours.sort_unstable();
+        theirs.sort_unstable();
+
+        ours == theirs
+    }
+
+    /// The length of the longest run of consecutive equal items, or `0` if
+    /// the vector is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 1, 2, 2, 2, 3]);
+    ///
+    /// assert_eq!(vector.max_run(), 3);
+    /// ```
+    pub fn max_run(&self) -> usize
+    where
+        T: PartialEq,
+    {
+        if self.is_empty() {
+            return 0;
+        }
+
+        let mut longest = 1;
+        let mut current = 1;
+
+        for pair in self.windows(2) {
+            if pair[0] == pair[1] {
+                current += 1;
+                longest = longest.max(current);
+            } else {
+                current = 1;
+            }
+        }
+
+        longest
+    }
+
+    /// Run-length encode the vector's items as `(item, count)` pairs,
+    /// failing if there are more than `M` runs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let vector = ArrayVec::from([1, 1, 2, 3, 3]);
+    ///
+    /// let runs = vector.rle_encode::<3>().unwrap();
+    /// assert_eq!(&runs, [(1, 2), (2, 1), (3, 2)].as_ref());
+    ///
+    /// assert_eq!(vector.rle_encode::<2>(), Err(CapacityError(())));
+    /// ```
+    pub fn rle_encode<const M: usize>(
+        &self,
+    ) -> Result<ArrayVec<(T, usize), { M }>, CapacityError<()>>
+    where
+        T: PartialEq + Copy,
+    {
+        let mut runs: ArrayVec<(T, usize), { M }> = ArrayVec::new();
+
+        for &item in self.iter() {
+            match runs.last_mut() {
+                Some((value, count)) if *value == item => *count += 1,
+                _ => runs.try_push((item, 1)).map_err(|_| CapacityError(()))?,
+            }
+        }
+
+        Ok(runs)
+    }
+
+    /// Count how many times each distinct item appears, failing if there are
+    /// more than `M` distinct items.
+    ///
+    /// Items are listed in the order they first appear. Every item is
+    /// compared against the distinct items seen so far, so this takes
+    /// `O(n²)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let vector = ArrayVec::from([1, 2, 1, 3, 2, 1]);
+    ///
+    /// let counts = vector.counts::<3>().unwrap();
+    /// assert_eq!(&counts, [(1, 3), (2, 2), (3, 1)].as_ref());
+    ///
+    /// assert_eq!(vector.counts::<2>(), Err(CapacityError(())));
+    /// ```
+    pub fn counts<const M: usize>(
+        &self,
+    ) -> Result<ArrayVec<(T, usize), { M }>, CapacityError<()>>
+    where
+        T: PartialEq + Copy,
+    {
+        let mut counts: ArrayVec<(T, usize), { M }> = ArrayVec::new();
+
+        for &item in self.iter() {
+            match counts.iter_mut().find(|(value, _)| *value == item) {
+                Some((_, count)) => *count += 1,
+                None => {
+                    counts.try_push((item, 1)).map_err(|_| CapacityError(()))?
+                },
+            }
+        }
+
+        Ok(counts)
+    }
+
+    /// Expand `(item, count)` pairs produced by [`ArrayVec::rle_encode()`]
+    /// back into a vector, failing if the decoded items won't fit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let pairs = [(1, 2), (2, 1), (3, 2)];
+    ///
+    /// let vector: ArrayVec<u32, 5> = ArrayVec::rle_decode(&pairs).unwrap();
+    /// assert_eq!(&vector, [1, 1, 2, 3, 3].as_ref());
+    ///
+    /// let too_small = ArrayVec::<u32, 4>::rle_decode(&pairs);
+    /// assert_eq!(too_small, Err(CapacityError(())));
+    /// ```
+    pub fn rle_decode(
+        pairs: &[(T, usize)],
+    ) -> Result<ArrayVec<T, { N }>, CapacityError<()>>
+    where
+        T: Clone,
+    {
+        let mut total: usize = 0;
+
+        for (_, count) in pairs {
+            match total.checked_add(*count) {
+                Some(sum) if sum <= N => total = sum,
+                _ => return Err(CapacityError(())),
+            }
+        }
+
+        let mut vector = ArrayVec::new();
+
+        for (item, count) in pairs {
+            for _ in 0..*count {
+                // Note: we've already checked the total length will fit
+                unsafe {
+                    vector.push_unchecked(item.clone());
+                }
+            }
+        }
+
+        Ok(vector)
+    }
+
+    /// Iterate over the indices where this vector and `other` differ.
+    ///
+    /// If one is longer than the other, every index past the end of the
+    /// shorter one counts as a difference.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3]);
+    ///
+    /// let changed: Vec<usize> = vector.diff_indices(&[1, 9, 3]).collect();
+    /// assert_eq!(changed, vec![1]);
+    ///
+    /// let changed: Vec<usize> = vector.diff_indices(&[1]).collect();
+    /// assert_eq!(changed, vec![1, 2]);
+    /// ```
+    pub fn diff_indices<'a>(
+        &'a self,
+        other: &'a [T],
+    ) -> impl Iterator<Item = usize> + 'a
+    where
+        T: PartialEq,
+    {
+        let shared = cmp::min(self.len(), other.len());
+        let longest = cmp::max(self.len(), other.len());
+
+        (0..longest).filter(move |&i| i >= shared || self[i] != other[i])
+    }
+
+    /// Find the index where `needle` first appears as a contiguous run of
+    /// items.
+    ///
+    /// An empty `needle` is found at index `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(vector.find_subsequence(&[2, 3]), Some(1));
+    /// assert_eq!(vector.find_subsequence(&[3, 2]), None);
+    /// ```
+    pub fn find_subsequence(&self, needle: &[T]) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        if needle.is_empty() {
+            // Note: windows() panics when given a size of 0
+            return Some(0);
+        }
+
+        self.windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    /// Find the first item which is equal to an earlier one, returning the
+    /// indices of the earlier item and the duplicate.
+    ///
+    /// Every pair of items is compared, so this takes `O(n²)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    ///
+    /// let vector = ArrayVec::from([1, 2, 3, 2]);
+    /// assert_eq!(vector.first_duplicate(), Some((1, 3)));
+    ///
+    /// let vector = ArrayVec::from([1, 2, 3]);
+    /// assert_eq!(vector.first_duplicate(), None);
+    /// ```
+    pub fn first_duplicate(&self) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        for (j, item) in self.iter().enumerate() {
+            if let Some(i) = self[..j].iter().position(|other| other == item) {
+                return Some((i, j));
+            }
+        }
+
+        None
+    }
+
+    /// Count the number of items satisfying a predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(vector.count_matching(|x| x % 2 == 0), 2);
+    /// ```
+    #[inline]
+    pub fn count_matching<F: FnMut(&T) -> bool>(
+        &self,
+        mut predicate: F,
+    ) -> usize {
+        self.iter().filter(|item| predicate(item)).count()
+    }
+
+    /// Check that every item satisfies a predicate, returning the index of
+    /// the first one which doesn't.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([2, 4, 5, 6]);
+    ///
+    /// assert_eq!(vector.validate(|x| x % 2 == 0), Err(2));
+    /// assert_eq!(vector.validate(|&x| x > 0), Ok(()));
+    /// ```
+    #[inline]
+    pub fn validate<F: FnMut(&T) -> bool>(
+        &self,
+        mut predicate: F,
+    ) -> Result<(), usize> {
+        match self.iter().position(|item| !predicate(item)) {
+            Some(index) => Err(index),
+            None => Ok(()),
+        }
+    }
+
+    /// Get a sorted copy of the vector, leaving the original untouched.
+    ///
+    /// The copy is sorted with [`slice::sort_unstable()`] so no allocations
+    /// are needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([3, 1, 2]);
+    ///
+    /// let sorted = vector.sorted();
+    ///
+    /// assert_eq!(&sorted, [1, 2, 3].as_ref());
+    /// assert_eq!(&vector, [3, 1, 2].as_ref());
+    /// ```
+    pub fn sorted(&self) -> ArrayVec<T, { N }>
+    where
+        T: Ord + Clone,
+    {
+        let mut copy = self.clone();
+        copy.sort_unstable();
+        copy
+    }
+
+    /// Check whether the items are in non-decreasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    ///
+    /// assert!(ArrayVec::from([1, 2, 2, 3]).is_sorted());
+    /// assert!(!ArrayVec::from([1, 3, 2]).is_sorted());
+    /// ```
+    #[inline]
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Check whether the items are sorted, using `in_order` to check that
+    /// each pair of neighbouring items is in the right order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([3, 2, 2, 1]);
+    ///
+    /// assert!(vector.is_sorted_by(|a, b| a >= b));
+    /// assert!(!vector.is_sorted_by(|a, b| a <= b));
+    /// ```
+    pub fn is_sorted_by<F>(&self, mut in_order: F) -> bool
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        self.windows(2).all(|pair| in_order(&pair[0], &pair[1]))
+    }
+
+    /// Check whether the items are sorted by the key `f` extracts from each
+    /// of them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from(["a", "bb", "cc", "ddd"]);
+    ///
+    /// assert!(vector.is_sorted_by_key(|word| word.len()));
+    /// ```
+    pub fn is_sorted_by_key<F, K>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&T) -> K,
+        K: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| f(a) <= f(b))
+    }
+
+    /// Find the index `item` should be inserted at to keep an already sorted
+    /// vector sorted.
+    ///
+    /// If the vector contains items equal to `item`, the index after them is
+    /// returned so insertions are stable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<u32, 4> = ArrayVec::new();
+    /// vector.try_extend_from_slice(&[1, 3, 5]).unwrap();
+    ///
+    /// let index = vector.sorted_insert_index(&4);
+    /// assert_eq!(index, 2);
+    ///
+    /// vector.insert(index, 4);
+    /// assert_eq!(&vector, [1, 3, 4, 5].as_ref());
+    /// ```
+    #[inline]
+    pub fn sorted_insert_index(&self, item: &T) -> usize
+    where
+        T: Ord,
+    {
+        self.partition_point(|existing| existing <= item)
+    }
+
+    /// Find the first item matching `pred`, returning the items before it and
+    /// the items after it.
+    ///
+    /// The matching item itself isn't included in either half.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 0, 3, 4]);
+    ///
+    /// let (before, after) = vector.split_once(|&item| item == 0).unwrap();
+    /// assert_eq!(before, &[1, 2]);
+    /// assert_eq!(after, &[3, 4]);
+    ///
+    /// assert!(vector.split_once(|&item| item > 4).is_none());
+    /// ```
+    pub fn split_once<F>(&self, pred: F) -> Option<(&[T], &[T])>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let index = self.iter().position(pred)?;
+
+        Some((&self[..index], &self[index + 1..]))
+    }
+
+    /// Split the vector into a fixed-size array containing the first `K`
+    /// items and a slice containing everything else, returning `None` if
+    /// there are fewer than `K` items.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// let (header, body) = vector.split_first_chunk::<2>().unwrap();
+    ///
+    /// assert_eq!(header, &[1, 2]);
+    /// assert_eq!(body, &[3, 4]);
+    /// assert!(vector.split_first_chunk::<5>().is_none());
+    /// ```
+    pub fn split_first_chunk<const K: usize>(&self) -> Option<(&[T; K], &[T])> {
+        if self.len() < K {
+            return None;
+        }
+
+        let (head, tail) = self.as_slice().split_at(K);
+
+        unsafe {
+            // Note: Safe because head contains exactly K items and a [T; K]
+            // has the same layout as K contiguous T's
+            Some((&*(head.as_ptr() as *const [T; K]), tail))
+        }
+    }
+
+    /// Get an array of references to the first `K` items, or `None` if there
+    /// are fewer than `K` items.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// let [first, second, third] = vector.ref_array::<3>().unwrap();
+    ///
+    /// assert_eq!((first, second, third), (&1, &2, &3));
+    /// assert!(vector.ref_array::<5>().is_none());
+    /// ```
+    pub fn ref_array<const K: usize>(&self) -> Option<[&T; K]> {
+        let (head, _) = self.split_first_chunk::<K>()?;
+
+        unsafe {
+            // Note: an array of uninitialized items is always valid
+            let mut refs: [MaybeUninit<&T>; K] =
+                MaybeUninit::uninit().assume_init();
+
+            for (slot, item) in refs.iter_mut().zip(head.iter()) {
+                *slot = MaybeUninit::new(item);
+            }
+
+            // every slot was initialized, and [MaybeUninit<&T>; K] has the
+            // same layout as [&T; K]
+            Some(ptr::read(refs.as_ptr() as *const [&T; K]))
+        }
+    }
+
+    /// The mutable version of [`ArrayVec::split_first_chunk()`].
+    pub fn split_first_chunk_mut<const K: usize>(
+        &mut self,
+    ) -> Option<(&mut [T; K], &mut [T])> {
+        if self.len() < K {
+            return None;
+        }
+
+        let (head, tail) = self.as_slice_mut().split_at_mut(K);
+
+        unsafe { Some((&mut *(head.as_mut_ptr() as *mut [T; K]), tail)) }
+    }
+
+    /// Split the vector into a slice and a fixed-size array containing the
+    /// last `K` items, returning `None` if there are fewer than `K` items.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// let (body, trailer) = vector.split_last_chunk::<2>().unwrap();
+    ///
+    /// assert_eq!(body, &[1, 2]);
+    /// assert_eq!(trailer, &[3, 4]);
+    /// assert!(vector.split_last_chunk::<5>().is_none());
+    /// ```
+    pub fn split_last_chunk<const K: usize>(&self) -> Option<(&[T], &[T; K])> {
+        if self.len() < K {
+            return None;
+        }
+
+        let (head, tail) = self.as_slice().split_at(self.len() - K);
+
+        unsafe { Some((head, &*(tail.as_ptr() as *const [T; K]))) }
+    }
+
+    /// The mutable version of [`ArrayVec::split_last_chunk()`].
+    pub fn split_last_chunk_mut<const K: usize>(
+        &mut self,
+    ) -> Option<(&mut [T], &mut [T; K])> {
+        if self.len() < K {
+            return None;
+        }
+
+        let split = self.len() - K;
+        let (head, tail) = self.as_slice_mut().split_at_mut(split);
+
+        unsafe { Some((head, &mut *(tail.as_mut_ptr() as *mut [T; K]))) }
+    }
+
+    /// View the vector as a slice of `K`-item chunks, plus a slice of
+    /// leftover items which don't fill a whole chunk.
+    ///
+    /// # Panics
+    ///
+    /// `K` must not be zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3, 4, 5]);
+    ///
+    /// let (chunks, remainder) = vector.as_chunks::<2>();
+    ///
+    /// assert_eq!(chunks, &[[1, 2], [3, 4]]);
+    /// assert_eq!(remainder, &[5]);
+    /// ```
+    pub fn as_chunks<const K: usize>(&self) -> (&[[T; K]], &[T]) {
+        assert!(K != 0, "The chunk size must be non-zero");
+
+        let num_chunks = self.len() / K;
+        let (chunks, remainder) = self.as_slice().split_at(num_chunks * K);
+
+        unsafe {
+            // Note: Safe because num_chunks * K items are initialized and a
+            // [T; K] has the same layout as K contiguous T's
+            let chunks = slice::from_raw_parts(
+                chunks.as_ptr() as *const [T; K],
+                num_chunks,
+            );
+
+            (chunks, remainder)
+        }
+    }
+
+    /// The mutable version of [`ArrayVec::as_chunks()`].
+    pub fn as_chunks_mut<const K: usize>(
+        &mut self,
+    ) -> (&mut [[T; K]], &mut [T]) {
+        assert!(K != 0, "The chunk size must be non-zero");
+
+        let num_chunks = self.len() / K;
+        let (chunks, remainder) =
+            self.as_slice_mut().split_at_mut(num_chunks * K);
+
+        unsafe {
+            let chunks = slice::from_raw_parts_mut(
+                chunks.as_mut_ptr() as *mut [T; K],
+                num_chunks,
+            );
+
+            (chunks, remainder)
+        }
+    }
+
+    /// Call `f` on each complete chunk of `K` items, in order.
+    ///
+    /// Any items left over at the end which don't make up a full chunk are
+    /// skipped. This is handy for things like block ciphers which operate on
+    /// fixed-size blocks of a buffer in place.
+    ///
+    /// # Panics
+    ///
+    /// `K` must be non-zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3, 4, 5]);
+    ///
+    /// vector.process_chunks(|block: &mut [u8; 2]| {
+    ///     for byte in block.iter_mut() {
+    ///         *byte ^= 0xF0;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(&vector, [0xF1, 0xF2, 0xF3, 0xF4, 5].as_ref());
+    /// ```
+    pub fn process_chunks<F, const K: usize>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut [T; K]),
+    {
+        let (chunks, _) = self.as_chunks_mut::<K>();
+
+        for chunk in chunks {
+            f(chunk);
+        }
+    }
+
+    /// Get a reference to the backing array, if every item is initialized
+    /// (i.e. the vector is full).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<u32, 3> = ArrayVec::new();
+    /// vector.push(1);
+    /// vector.push(2);
+    ///
+    /// assert_eq!(vector.as_full_array(), None);
+    ///
+    /// vector.push(3);
+    ///
+    /// assert_eq!(vector.as_full_array(), Some(&[1, 2, 3]));
+    /// ```
+    #[inline]
+    pub fn as_full_array(&self) -> Option<&[T; N]> {
+        if self.is_full() {
+            // Note: Safe because every item is initialized and a [T; N] is
+            // identical to [MaybeUninit<T>; N]
+            unsafe { Some(&*(self.as_ptr() as *const [T; N])) }
+        } else {
+            None
+        }
+    }
+
+    /// The mutable version of [`ArrayVec::as_full_array()`].
+    #[inline]
+    pub fn as_full_array_mut(&mut self) -> Option<&mut [T; N]> {
+        if self.is_full() {
+            unsafe { Some(&mut *(self.as_mut_ptr() as *mut [T; N])) }
+        } else {
+            None
+        }
+    }
+
+    /// Shorten the vector to `new_length` items, moving the removed items
+    /// into a new [`ArrayVec`] instead of dropping them.
+    ///
+    /// # Panics
+    ///
+    /// The removed items must fit in an `ArrayVec<T, M>`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector = ArrayVec::from([1, 2, 3, 4, 5]);
+    ///
+    /// let tail: ArrayVec<u32, 3> = vector.truncate_returning(2);
+    ///
+    /// assert_eq!(&vector, [1, 2].as_ref());
+    /// assert_eq!(&tail, [3, 4, 5].as_ref());
+    /// ```
+    pub fn truncate_returning<const M: usize>(
+        &mut self,
+        new_length: usize,
+    ) -> ArrayVec<T, { M }> {
+        let mut tail = ArrayVec::new();
+        let len = self.len();
+
+        if new_length >= len {
+            return tail;
+        }
+
+        let tail_length = len - new_length;
+        if tail_length > M {
+            capacity_exceeded!("truncate_returning", tail_length, M);
+        }
+
+        unsafe {
+            // ownership of the tail is transferred to the new vector, so make
+            // sure we forget about them before copying
+            self.set_len(new_length);
+            ptr::copy_nonoverlapping(
+                self.as_ptr().add(new_length),
+                tail.as_mut_ptr(),
+                tail_length,
+            );
+            tail.set_len(tail_length);
+        }
+
+        tail
+    }
+
+    /// Consume the vector, moving the items before `at` into one
+    /// [`ArrayVec`] and the rest into another.
+    ///
+    /// # Panics
+    ///
+    /// `at` must not be greater than the vector's length, and each half must
+    /// fit in its new vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// let (head, tail): (ArrayVec<u32, 1>, ArrayVec<u32, 5>) =
+    ///     vector.into_split(1);
+    ///
+    /// assert_eq!(&head, [1].as_ref());
+    /// assert_eq!(&tail, [2, 3, 4].as_ref());
+    /// ```
+    pub fn into_split<const A: usize, const B: usize>(
+        mut self,
+        at: usize,
+    ) -> (ArrayVec<T, { A }>, ArrayVec<T, { B }>) {
+        let len = self.len();
+
+        if at > len {
+            out_of_bounds!("into_split", at, len);
+        }
+        if at > A {
+            capacity_exceeded!("into_split", at, A);
+        }
+        if len - at > B {
+            capacity_exceeded!("into_split", len - at, B);
+        }
+
+        let mut head = ArrayVec::new();
+        let mut tail = ArrayVec::new();
+
+        unsafe {
+            // all items are about to be moved out, so make sure our destructor
+            // won't touch them
+            self.set_len(0);
+
+            ptr::copy_nonoverlapping(self.as_ptr(), head.as_mut_ptr(), at);
+            head.set_len(at);
+
+            ptr::copy_nonoverlapping(
+                self.as_ptr().add(at),
+                tail.as_mut_ptr(),
+                len - at,
+            );
+            tail.set_len(len - at);
+        }
+
+        (head, tail)
+    }
+
+    /// Move the items into a vector with a different capacity, giving the
+    /// original vector back if they won't fit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut vector: ArrayVec<u32, 4> = ArrayVec::new();
+    /// vector.push(1);
+    /// vector.push(2);
+    ///
+    /// let bigger: ArrayVec<u32, 8> = vector.recapacity().unwrap();
+    /// assert_eq!(&bigger, [1, 2].as_ref());
+    ///
+    /// let smaller: ArrayVec<u32, 2> = bigger.recapacity().unwrap();
+    /// assert_eq!(&smaller, [1, 2].as_ref());
+    ///
+    /// // but the items won't fit in a vector with capacity 1
+    /// let original = smaller.recapacity::<1>().unwrap_err();
+    /// assert_eq!(&original, [1, 2].as_ref());
+    /// ```
+    pub fn recapacity<const M: usize>(
+        mut self,
+    ) -> Result<ArrayVec<T, { M }>, Self> {
+        let len = self.len();
+
+        if len > M {
+            return Err(self);
+        }
+
+        let mut other = ArrayVec::new();
+
+        unsafe {
+            // ownership is being transferred to the new vector, so make sure
+            // our destructor won't touch the items
+            self.set_len(0);
+            ptr::copy_nonoverlapping(self.as_ptr(), other.as_mut_ptr(), len);
+            other.set_len(len);
+        }
+
+        Ok(other)
+    }
+
+    /// Move the first `count` items onto the end of `dst`, shifting the
+    /// remaining items to the front.
+    ///
+    /// Fails without moving anything if there are fewer than `count` items
+    /// or `dst` doesn't have room for them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut vector = ArrayVec::from([1, 2, 3]);
+    /// let mut dst: ArrayVec<u32, 4> = ArrayVec::new();
+    ///
+    /// vector.transfer_front(2, &mut dst).unwrap();
+    ///
+    /// assert_eq!(&vector, [3].as_ref());
+    /// assert_eq!(&dst, [1, 2].as_ref());
+    ///
+    /// assert_eq!(vector.transfer_front(2, &mut dst), Err(CapacityError(())));
+    /// ```
+    pub fn transfer_front<const M: usize>(
+        &mut self,
+        count: usize,
+        dst: &mut ArrayVec<T, { M }>,
+    ) -> Result<(), CapacityError<()>> {
+        let len = self.len();
+
+        if count > len || dst.remaining_capacity() < count {
+            return Err(CapacityError(()));
+        }
+
+        unsafe {
+            // the items are about to be moved around, so temporarily forget
+            // about them
+            self.set_len(0);
+
+            // move the front items across to dst
+            let dst_len = dst.len();
+            ptr::copy_nonoverlapping(
+                self.as_ptr(),
+                dst.as_mut_ptr().add(dst_len),
+                count,
+            );
+            dst.set_len(dst_len + count);
+
+            // then shift everything else forward to fill the gap
+            ptr::copy(self.as_ptr().add(count), self.as_mut_ptr(), len - count);
+            self.set_len(len - count);
+        }
+
+        Ok(())
+    }
+
+    /// Reinterpret the items as another type with the same size and
+    /// alignment, reusing them without any conversion.
+    ///
+    /// Using types with a different size or alignment is a compile error.
+    ///
+    /// # Safety
+    ///
+    /// Every item must be a valid `U` when its bytes are reinterpreted (see
+    /// [`core::mem::transmute()`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector: ArrayVec<u32, 2> = ArrayVec::from([1, u32::max_value()]);
+    ///
+    /// let signed: ArrayVec<i32, 2> = unsafe { vector.transmute_elements() };
+    ///
+    /// assert_eq!(&signed, [1, -1].as_ref());
+    /// ```
+    ///
+    /// A `u64` is bigger than a `u32`, so this won't compile.
+    ///
+    /// ```rust,compile_fail
+    /// use const_arrayvec::ArrayVec;
+    /// let vector: ArrayVec<u32, 2> = ArrayVec::from([1, 2]);
+    ///
+    /// let _: ArrayVec<u64, 2> = unsafe { vector.transmute_elements() };
+    /// ```
+    pub unsafe fn transmute_elements<U>(mut self) -> ArrayVec<U, { N }> {
+        // Note: evaluating the constant is enough to trigger the check
+        let () = AssertSameLayout::<T, U>::OK;
+
+        let len = self.len();
+        let mut other = ArrayVec::new();
+
+        // ownership is being transferred to the new vector, so make sure our
+        // destructor won't touch the items
+        self.set_len(0);
+        ptr::copy_nonoverlapping(
+            self.as_ptr() as *const U,
+            other.as_mut_ptr(),
+            len,
+        );
+        other.set_len(len);
+
+        other
+    }
+
+    /// Group the items into arrays of `K`, giving the original vector back
+    /// if the length isn't a multiple of `K` or the arrays won't fit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// let chunks: ArrayVec<[u32; 2], 2> = vector.chunked().unwrap();
+    /// assert_eq!(&chunks, [[1, 2], [3, 4]].as_ref());
+    ///
+    /// // 4 items can't be split into arrays of 3
+    /// let vector = ArrayVec::from([1, 2, 3, 4]);
+    /// let original = vector.chunked::<3, 2>().unwrap_err();
+    /// assert_eq!(&original, [1, 2, 3, 4].as_ref());
+    /// ```
+    pub fn chunked<const K: usize, const O: usize>(
+        mut self,
+    ) -> Result<ArrayVec<[T; K], { O }>, Self> {
+        let len = self.len();
+
+        if K == 0 {
+            return Err(self);
+        }
+
+        let count = len / K;
+        let leftovers = len % K;
+
+        if leftovers != 0 || count > O {
+            return Err(self);
+        }
+
+        let mut chunks = ArrayVec::new();
+
+        unsafe {
+            // ownership is being transferred to the new vector, so make sure
+            // our destructor won't touch the items
+            self.set_len(0);
+
+            // an array of arrays has the same layout as one long array
+            ptr::copy_nonoverlapping(
+                self.as_ptr(),
+                chunks.as_mut_ptr() as *mut T,
+                len,
+            );
+            chunks.set_len(count);
+        }
+
+        Ok(chunks)
+    }
+}
+
+impl<A, B, const N: usize> ArrayVec<(A, B), { N }> {
+    /// Consume a vector of pairs, splitting it into a vector of the first
+    /// items and a vector of the second items.
+    ///
+    /// # Panics
+    ///
+    /// Both new vectors must have enough capacity for all the pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([(1, 'a'), (2, 'b')]);
+    ///
+    /// let (numbers, letters): (ArrayVec<u32, 2>, ArrayVec<char, 4>) =
+    ///     vector.unzip();
+    ///
+    /// assert_eq!(&numbers, [1, 2].as_ref());
+    /// assert_eq!(&letters, ['a', 'b'].as_ref());
+    /// ```
+    pub fn unzip<const P: usize, const Q: usize>(
+        mut self,
+    ) -> (ArrayVec<A, { P }>, ArrayVec<B, { Q }>) {
+        let len = self.len();
+
+        if len > P {
+            capacity_exceeded!("unzip", len, P);
+        }
+        if len > Q {
+            capacity_exceeded!("unzip", len, Q);
+        }
+
+        let mut left = ArrayVec::new();
+        let mut right = ArrayVec::new();
+
+        unsafe {
+            // all pairs are about to be moved out, so make sure our
+            // destructor won't touch them
+            self.set_len(0);
+
+            for i in 0..len {
+                let (a, b) = self.as_ptr().add(i).read();
+                left.push_unchecked(a);
+                right.push_unchecked(b);
+            }
+        }
+
+        (left, right)
+    }
+}
+
+impl<T, const K: usize, const N: usize> ArrayVec<[T; K], { N }> {
+    /// Consume a vector of arrays, concatenating them into a single vector.
+    ///
+    /// # Panics
+    ///
+    /// The new vector must have enough capacity for every item in every
+    /// array.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from([[1, 2], [3, 4]]);
+    ///
+    /// let flat: ArrayVec<u32, 4> = vector.flatten();
+    ///
+    /// assert_eq!(&flat, [1, 2, 3, 4].as_ref());
+    /// ```
+    pub fn flatten<const O: usize>(mut self) -> ArrayVec<T, { O }> {
+        let count = self.len() * K;
+
+        if count > O {
+            capacity_exceeded!("flatten", count, O);
+        }
+
+        let mut flat = ArrayVec::new();
+
+        unsafe {
+            // ownership is being transferred to the new vector, so make sure
+            // our destructor won't touch the items
+            self.set_len(0);
+
+            // arrays are laid out contiguously, so the whole vector can be
+            // copied across as one big run of T's
+            ptr::copy_nonoverlapping(
+                self.as_ptr() as *const T,
+                flat.as_mut_ptr(),
+                count,
+            );
+            flat.set_len(count);
+        }
+
+        flat
+    }
+}
+
+impl<const N: usize> ArrayVec<u8, { N }> {
+    put_methods! {
+        /// Append a `u16` in big-endian byte order, if there is room.
+        put_u16_be(u16) => to_be_bytes;
+        /// Append a `u16` in little-endian byte order, if there is room.
+        put_u16_le(u16) => to_le_bytes;
+        /// Append a `u32` in big-endian byte order, if there is room.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use const_arrayvec::{ArrayVec, CapacityError};
+        /// let mut buffer: ArrayVec<u8, 6> = ArrayVec::new();
+        ///
+        /// buffer.put_u32_be(0xDEADBEEF).unwrap();
+        /// assert_eq!(&buffer, [0xDE, 0xAD, 0xBE, 0xEF].as_ref());
+        ///
+        /// assert_eq!(buffer.put_u32_be(42), Err(CapacityError(())));
+        /// ```
+        put_u32_be(u32) => to_be_bytes;
+        /// Append a `u32` in little-endian byte order, if there is room.
+        put_u32_le(u32) => to_le_bytes;
+        /// Append a `u64` in big-endian byte order, if there is room.
+        put_u64_be(u64) => to_be_bytes;
+        /// Append a `u64` in little-endian byte order, if there is room.
+        put_u64_le(u64) => to_le_bytes;
+    }
+
+    /// Append `count` copies of `byte`, if there is room.
+    ///
+    /// The bytes are written in one go (i.e. `memset()`) instead of being
+    /// pushed one at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut buffer: ArrayVec<u8, 4> = ArrayVec::new();
+    /// buffer.push(1);
+    ///
+    /// buffer.try_push_repeated(0xFF, 2).unwrap();
+    /// assert_eq!(&buffer, [1, 0xFF, 0xFF].as_ref());
+    ///
+    /// assert_eq!(buffer.try_push_repeated(0, 2), Err(CapacityError(())));
+    /// ```
+    pub fn try_push_repeated(
+        &mut self,
+        byte: u8,
+        count: usize,
+    ) -> Result<(), CapacityError<()>> {
+        if self.remaining_capacity() < count {
+            return Err(CapacityError(()));
+        }
+
+        let len = self.len();
+
+        unsafe {
+            // we've already made sure it'll fit
+            ptr::write_bytes(self.as_mut_ptr().add(len), byte, count);
+            self.set_len(len + count);
+        }
+
+        Ok(())
+    }
+
+    /// Insert copies of `fill` at the front of the vector until it is
+    /// `target_len` bytes long, as is common with fixed-width encodings.
+    ///
+    /// Nothing happens if the vector is already long enough, and an error is
+    /// returned if `target_len` is larger than the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::{ArrayVec, CapacityError};
+    /// let mut buffer: ArrayVec<u8, 4> = ArrayVec::new();
+    /// buffer.try_extend_from_slice(&[1, 2]).unwrap();
+    ///
+    /// buffer.left_pad(4, 0).unwrap();
+    /// assert_eq!(&buffer, [0, 0, 1, 2].as_ref());
+    ///
+    /// assert_eq!(buffer.left_pad(5, 0), Err(CapacityError(())));
+    /// ```
+    pub fn left_pad(
+        &mut self,
+        target_len: usize,
+        fill: u8,
+    ) -> Result<(), CapacityError<()>> {
+        if target_len > N {
+            return Err(CapacityError(()));
+        }
+
+        let len = self.len();
+        if len >= target_len {
+            return Ok(());
+        }
+
+        let padding = target_len - len;
+
+        unsafe {
+            // shift the existing bytes back to make room, then fill the gap
+            let start = self.as_mut_ptr();
+            ptr::copy(start, start.add(padding), len);
+            ptr::write_bytes(start, fill, padding);
+            self.set_len(target_len);
+        }
+
+        Ok(())
+    }
+
+    /// Consume a full vector, returning its bytes as a fixed-size array.
+    ///
+    /// This is useful when something (e.g. a hash digest) was built up
+    /// incrementally but needs to be handed out as a `[u8; N]`. If the
+    /// vector isn't full, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let mut digest: ArrayVec<u8, 4> = ArrayVec::new();
+    /// digest.try_extend_from_slice(&[0xDE, 0xAD]).unwrap();
+    /// assert_eq!(digest.clone().into_byte_array(), None);
+    ///
+    /// digest.try_extend_from_slice(&[0xBE, 0xEF]).unwrap();
+    /// assert_eq!(digest.into_byte_array(), Some([0xDE, 0xAD, 0xBE, 0xEF]));
+    /// ```
+    #[inline]
+    pub fn into_byte_array(self) -> Option<[u8; N]> {
+        self.as_full_array().copied()
+    }
+
+    /// Get something which will display the vector's contents as UTF-8,
+    /// replacing any invalid sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let greeting = ArrayVec::from([104, 105]);
+    /// assert_eq!(greeting.display_utf8_lossy().to_string(), "hi");
+    ///
+    /// let invalid = ArrayVec::from([104, 0xFF, 105]);
+    /// assert_eq!(invalid.display_utf8_lossy().to_string(), "h\u{FFFD}i");
+    /// ```
+    #[inline]
+    pub fn display_utf8_lossy(&self) -> Utf8Lossy<'_> {
+        Utf8Lossy::new(self.as_slice())
+    }
+
+    /// Check whether the vector contains exactly the same bytes as `other`.
+    ///
+    /// The lengths are compared first, with the contents then being compared
+    /// as a single block of memory (i.e. `memcmp()`) instead of item by item.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from(*b"GET /");
+    ///
+    /// assert!(vector.bytewise_eq(b"GET /"));
+    /// assert!(!vector.bytewise_eq(b"PUT /"));
+    /// assert!(!vector.bytewise_eq(b"GET"));
+    /// ```
+    #[inline]
+    pub fn bytewise_eq(&self, other: &[u8]) -> bool {
+        // Note: core specialises [u8] equality to a memcmp()
+        self.len() == other.len() && self.as_slice() == other
+    }
+
+    /// If the vector starts with `prefix`, get the bytes which come after it.
+    ///
+    /// This is handy when dispatching on a protocol's command or magic
+    /// number, where the rest of the frame is only interesting once the
+    /// prefix has matched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let request = ArrayVec::from(*b"GET /");
+    ///
+    /// assert_eq!(request.strip_prefix(b"GET "), Some(b"/".as_ref()));
+    /// assert_eq!(request.strip_prefix(b"PUT "), None);
+    /// ```
+    #[inline]
+    pub fn strip_prefix(&self, prefix: &[u8]) -> Option<&[u8]> {
+        if self.starts_with(prefix) {
+            Some(&self.as_slice()[prefix.len()..])
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the sections of the vector separated by `delimiter`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from(*b"a,b,c");
+    ///
+    /// let fields: Vec<&[u8]> = vector.split_on(b',').collect();
+    ///
+    /// assert_eq!(fields, vec![b"a", b"b", b"c"]);
+    /// ```
+    #[inline]
+    pub fn split_on(&self, delimiter: u8) -> impl Iterator<Item = &[u8]> + '_ {
+        self.as_slice().split(move |&byte| byte == delimiter)
+    }
+
+    /// Get a [`ByteReader`] for reading values back out of the vector.
+    #[inline]
+    pub fn reader(&self) -> ByteReader<'_> { ByteReader::new(self.as_slice()) }
+
+    /// Calculate the 64-bit [FNV-1a][fnv] hash of the vector's contents.
+    ///
+    /// Unlike [`Hash`], the result is the same across runs and platforms so
+    /// it can be used as a simple checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from(*b"hello");
+    ///
+    /// assert_eq!(vector.fnv1a_hash(), 0xa430d84680aabd0b);
+    /// ```
+    ///
+    /// [fnv]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+    pub fn fnv1a_hash(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+
+        self.iter().fold(OFFSET_BASIS, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+    }
+
+    /// Get the vector's [`ArrayVec::fnv1a_hash()`] as little-endian bytes,
+    /// for use as a compact key when deduplicating data.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::ArrayVec;
+    /// let vector = ArrayVec::from(*b"abc");
+    ///
+    /// assert_eq!(
+    ///     vector.digest64(),
+    ///     [0x4b, 0x57, 0x41, 0x05, 0x19, 0xa2, 0x1f, 0xe7]
+    /// );
+    /// ```
+    #[inline]
+    pub fn digest64(&self) -> [u8; 8] { self.fnv1a_hash().to_le_bytes() }
+}
+
+impl<T, const N: usize> Deref for ArrayVec<T, { N }> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayVec<T, { N }> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len()) }
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayVec<T, { N }> {
+    /// Makes sure all items are cleaned up once you're done with the
+    /// [`ArrayVec`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::{mem, sync::atomic::{AtomicUsize, Ordering}};
+    /// use const_arrayvec::ArrayVec;
+    ///
+    /// // create a dummy type which increments a number when dropped
+    ///
+    /// struct OnDropped<'a>(&'a AtomicUsize);
+    ///
+    /// impl<'a> Drop for OnDropped<'a> {
+    ///   fn drop(&mut self) { self.0.fetch_add(1, Ordering::Relaxed); }
+    /// }
+    ///
+    /// // create our vector
+    /// let mut vector: ArrayVec<OnDropped<'_>, 5> = ArrayVec::new();
+    ///
+    /// // then set up our counter
+    /// let counter = AtomicUsize::new(0);
+    ///
+    /// // and add a couple `OnDropped`'s to the vector
+    /// vector.push(OnDropped(&counter));
+    /// vector.push(OnDropped(&counter));
+    /// vector.push(OnDropped(&counter));
+    ///
+    /// // the vector is still live so our counter shouldn't have changed
+    /// assert_eq!(counter.load(Ordering::Relaxed), 0);
+    ///
+    /// // explicitly drop the vector
+    /// mem::drop(vector);
+    ///
+    /// // and the counter should have updated
+    /// assert_eq!(counter.load(Ordering::Relaxed), 3);
+    /// ```
+    #[inline]
+    fn drop(&mut self) {
+        // Makes sure the destructors for all items are run.
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for ArrayVec<T, { N }> {
+    #[inline]
+    fn as_ref(&self) -> &[T] { self.as_slice() }
+}
+
+impl<T, const N: usize> AsMut<[T]> for ArrayVec<T, { N }> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [T] { self.as_slice_mut() }
+}
+
+impl<T: Debug, const N: usize> Debug for ArrayVec<T, { N }> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<ArrayVec<T, { M }>>
+    for ArrayVec<T, { N }>
+{
+    #[inline]
+    fn eq(&self, other: &ArrayVec<T, { M }>) -> bool {
+        // vectors of different lengths can never be equal, so bail early
+        self.len() == other.len() && self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq<[T]> for ArrayVec<T, { N }> {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool { self.as_slice() == other }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayVec<T, { N }> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for ArrayVec<T, { N }> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for ArrayVec<T, { N }> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for ArrayVec<T, { N }> {
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) { self.as_slice().hash(hasher); }
+}
+
+impl<T, const N: usize> Default for ArrayVec<T, { N }> {
+    #[inline]
+    fn default() -> Self { ArrayVec::new() }
+}
+
+impl<Ix, T, const N: usize> Index<Ix> for ArrayVec<T, { N }>
+where
+    [T]: Index<Ix>,
+{
+    type Output = <[T] as Index<Ix>>::Output;
+
+    #[inline]
+    fn index(&self, ix: Ix) -> &Self::Output { self.as_slice().index(ix) }
+}
+
+impl<Ix, T, const N: usize> IndexMut<Ix> for ArrayVec<T, { N }>
+where
+    [T]: IndexMut<Ix>,
+{
+    #[inline]
+    fn index_mut(&mut self, ix: Ix) -> &mut Self::Output {
+        self.as_slice_mut().index_mut(ix)
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayVec<T, { N }> {
+    fn clone(&self) -> ArrayVec<T, { N }> {
+        let mut other: ArrayVec<T, { N }> = ArrayVec::new();
+
+        for item in self.as_slice() {
+            unsafe {
+                // if it fit into the original, it'll fit into the clone
+                other.push_unchecked(item.clone());
+            }
+        }
+
+        other
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ArrayVec<T, { N }> {
+    fn from(other: [T; N]) -> ArrayVec<T, { N }> {
+        let mut vec = ArrayVec::<T, { N }>::new();
+
+        unsafe {
+            // Copy the items from the array directly to the backing buffer
+
+            // Note: Safe because a [T; N] is identical to [MaybeUninit<T>; N]
+            ptr::copy_nonoverlapping(
+                other.as_ptr(),
+                vec.as_mut_ptr(),
+                other.len(),
+            );
+            // ownership has been transferred to the backing buffer, make sure
+            // the original array's destructors aren't called prematurely
+            mem::forget(other);
+            // the memory has now been initialized so it's safe to set the
+            // length
+            vec.set_len(N);
+        }
+
+        vec
+    }
+}
+
+/// A guard which truncates a vector back to a known length when dropped, used
+/// to undo partially completed operations if something panics.
+struct TruncateOnDrop<'a, T, const N: usize> {
+    vector: &'a mut ArrayVec<T, { N }>,
+    length: usize,
+}
+
+impl<'a, T, const N: usize> Drop for TruncateOnDrop<'a, T, { N }> {
+    fn drop(&mut self) { self.vector.truncate(self.length); }
+}
+
+/// A compile-time check that `T` and `U` have the same size and alignment.
+struct AssertSameLayout<T, U>(PhantomData<(T, U)>);
+
+impl<T, U> AssertSameLayout<T, U> {
+    const OK: () = assert!(
+        mem::size_of::<T>() == mem::size_of::<U>()
+            && mem::align_of::<T>() == mem::align_of::<U>(),
+        "The item types must have the same size and alignment"
+    );
+}
+
+/// The error returned when there isn't enough space to add another item.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> Display for CapacityError<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Insufficient capacity")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArrayVec;
+    use core::{cell::Cell, mem};
+
+    /// Increments a counter when dropped.
+    #[derive(Debug)]
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl<'a> Drop for DropCounter<'a> {
+        fn drop(&mut self) { self.0.set(self.0.get() + 1); }
+    }
+
+    #[test]
+    fn test_equal_to_expected_slice() {
+        let mut vector: ArrayVec<u8, 10> = ArrayVec::new();
+        vector.push(0);
+        vector.push(1);
+        vector.push(2);
+        assert_eq!(vector.len(), 3);
+
+        vector.try_insert(3, 3).unwrap();
+
+        assert_eq!(vector.as_slice(), &[0, 1, 2, 3]);
+        assert_eq!(vector.capacity(), 10);
+    }
+
+    #[test]
+    fn test_force_insert_and_remove() {
+        let mut vector: ArrayVec<u8, 2> = ArrayVec::new();
+
+        // force_insert
+        vector.force_insert(0, 2);
+        vector.force_insert(1, 4);
+        vector.force_insert(0, 4);
+        assert_eq!(vector.as_slice(), &[4, 2]);
+
+        // remove
+        assert_eq!(vector.remove(0), 4);
+        assert_eq!(vector.as_slice(), &[2]);
+        assert_eq!(vector.try_remove(1), None);
+        assert_eq!(vector.remove(0), 2);
+        assert_eq!(vector.len(), 0);
+
+        // swap_remove
+        vector = ArrayVec::from([2, 4]);
+        assert_eq!(vector.swap_remove(0), 2);
+        assert_eq!(vector.as_slice(), &[4]);
+        assert_eq!(vector.try_swap_remove(1), None);
+        assert_eq!(vector.swap_remove(0), 4);
+        assert_eq!(vector.len(), 0);
+    }
+
+    #[test]
+    fn test_swap_contents_with_different_lengths() {
+        let mut first: ArrayVec<u8, 4> = ArrayVec::from([1, 2, 3, 4]);
+        let mut second: ArrayVec<u8, 4> = ArrayVec::new();
+        second.push(5);
+
+        first.swap_contents(&mut second);
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(first.as_slice(), &[5]);
+        assert_eq!(second.len(), 4);
+        assert_eq!(second.as_slice(), &[1, 2, 3, 4]);
+
+        // and swapping back restores the originals
+        second.swap_contents(&mut first);
+        assert_eq!(first.as_slice(), &[1, 2, 3, 4]);
+        assert_eq!(second.as_slice(), &[5]);
+    }
+
+    #[test]
+    fn test_split_chunks() {
+        let mut vector: ArrayVec<u8, 6> = ArrayVec::new();
+        vector.try_extend_from_slice(&[1, 2, 3, 4]).unwrap();
+
+        let (head, rest) = vector.split_first_chunk::<2>().unwrap();
+        assert_eq!(head, &[1, 2]);
+        assert_eq!(rest, &[3, 4]);
+
+        let (rest, tail) = vector.split_last_chunk::<2>().unwrap();
+        assert_eq!(rest, &[1, 2]);
+        assert_eq!(tail, &[3, 4]);
+
+        {
+            let (head, rest) = vector.split_first_chunk_mut::<2>().unwrap();
+            head[0] = 10;
+            rest[0] = 30;
+        }
+        {
+            let (rest, tail) = vector.split_last_chunk_mut::<2>().unwrap();
+            rest[1] = 20;
+            tail[1] = 40;
+        }
+        assert_eq!(vector.as_slice(), &[10, 20, 30, 40]);
+
+        // asking for more items than we have fails
+        assert!(vector.split_first_chunk::<5>().is_none());
+        assert!(vector.split_last_chunk_mut::<5>().is_none());
+        assert_eq!(vector.split_first_chunk::<4>().unwrap().1, &[]);
+    }
+
+    #[test]
+    fn test_truncate_returning_transfers_ownership() {
+        let counter = Cell::new(0);
+        let mut vector: ArrayVec<DropCounter<'_>, 5> = ArrayVec::new();
+        for _ in 0..5 {
+            vector.push(DropCounter(&counter));
+        }
+
+        let tail: ArrayVec<DropCounter<'_>, 3> = vector.truncate_returning(2);
+
+        // nothing should have been dropped, just moved
+        assert_eq!(counter.get(), 0);
+        assert_eq!(vector.len(), 2);
+        assert_eq!(tail.len(), 3);
+
+        drop(tail);
+        assert_eq!(counter.get(), 3);
+        drop(vector);
+        assert_eq!(counter.get(), 5);
+    }
+
+    #[test]
+    fn test_truncate_returning_past_the_end_is_a_noop() {
+        let mut vector = ArrayVec::from([1, 2, 3]);
+
+        let tail: ArrayVec<u32, 0> = vector.truncate_returning(3);
+
+        assert!(tail.is_empty());
+        assert_eq!(vector.as_slice(), &[1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_truncate_returning_into_a_small_vector() {
+        let mut vector = ArrayVec::from([1, 2, 3]);
+
+        let _: ArrayVec<u32, 1> = vector.truncate_returning(1);
+    }
+
+    #[test]
+    fn test_capacity_math_in_a_const_context() {
+        const REMAINING: usize = {
+            let vector: ArrayVec<u8, 4> = ArrayVec::new();
+            let remaining = vector.remaining_capacity();
+            assert!(vector.as_uninit_len() == 4);
+            // destructors can't be run at compile time
+            mem::forget(vector);
+            remaining
+        };
+
+        assert_eq!(REMAINING, 4);
+    }
+
+    #[test]
+    fn test_into_split_transfers_ownership() {
+        let counter = Cell::new(0);
+        let mut vector: ArrayVec<DropCounter<'_>, 4> = ArrayVec::new();
+        for _ in 0..4 {
+            vector.push(DropCounter(&counter));
+        }
+
+        let (head, tail): (ArrayVec<_, 1>, ArrayVec<_, 3>) =
+            vector.into_split(1);
+
+        // nothing should have been dropped, just moved
+        assert_eq!(counter.get(), 0);
+        assert_eq!(head.len(), 1);
+        assert_eq!(tail.len(), 3);
+
+        drop(head);
+        assert_eq!(counter.get(), 1);
+        drop(tail);
+        assert_eq!(counter.get(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_into_split_with_a_half_that_doesnt_fit() {
+        let vector = ArrayVec::from([1, 2, 3, 4]);
+
+        let _: (ArrayVec<u32, 4>, ArrayVec<u32, 2>) = vector.into_split(1);
+    }
+
+    #[test]
+    fn test_last_mut_or_push_with() {
+        let mut vector: ArrayVec<u8, 2> = ArrayVec::new();
+        assert_eq!(*vector.last_mut_or_push_with(|| 7), 7);
+        assert_eq!(vector.as_slice(), &[7]);
+
+        let mut vector = ArrayVec::from([1, 2]);
+        *vector.last_mut_or_push_with(|| unreachable!()) = 5;
+        assert_eq!(vector.as_slice(), &[1, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_last_mut_or_push_with_no_capacity() {
+        let mut vector: ArrayVec<u8, 0> = ArrayVec::new();
+
+        vector.last_mut_or_push_with(|| 7);
+    }
+
+    #[test]
+    fn test_bytewise_eq_on_large_buffers() {
+        let mut first: ArrayVec<u8, 4096> = ArrayVec::new();
+        let mut second: ArrayVec<u8, 4096> = ArrayVec::new();
+        for i in 0..4096 {
+            first.push(i as u8);
+            second.push(i as u8);
+        }
+
+        for _ in 0..1000 {
+            assert!(first.bytewise_eq(&second));
+        }
+
+        // only the very last byte differs
+        second[4095] = 0;
+        assert!(!first.bytewise_eq(&second));
+        assert!(!first.bytewise_eq(&second[..4095]));
+        assert!(first.bytewise_eq(&first.clone()));
+    }
+
+    #[test]
+    fn test_try_clone_append_rolls_back_when_clone_panics() {
+        extern crate std;
+
+        #[derive(Debug)]
+        struct PanicOnClone<'a> {
+            id: u32,
+            drops: &'a Cell<usize>,
+        }
+
+        impl<'a> Clone for PanicOnClone<'a> {
+            fn clone(&self) -> Self {
+                if self.id == 1 {
+                    panic!("Can't clone item {}", self.id);
+                }
+                PanicOnClone {
+                    id: self.id,
+                    drops: self.drops,
+                }
+            }
+        }
+
+        impl<'a> Drop for PanicOnClone<'a> {
+            fn drop(&mut self) { self.drops.set(self.drops.get() + 1); }
+        }
+
+        let drops = Cell::new(0);
+        let items = [
+            PanicOnClone {
+                id: 0,
+                drops: &drops,
+            },
+            PanicOnClone {
+                id: 1,
+                drops: &drops,
+            },
+            PanicOnClone {
+                id: 2,
+                drops: &drops,
+            },
+        ];
+        let mut vector: ArrayVec<PanicOnClone<'_>, 5> = ArrayVec::new();
+        vector.push(PanicOnClone {
+            id: 42,
+            drops: &drops,
+        });
+
+        let got =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                vector.try_clone_append(&items)
+            }));
+
+        assert!(got.is_err());
+        assert_eq!(vector.len(), 1);
+        assert_eq!(vector[0].id, 42);
+        // the first item's clone should have been cleaned up
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn test_min_and_max() {
+        let vector = ArrayVec::from([3, 1, 4, 1, 5]);
+
+        assert_eq!(vector.min_item(), Some(&1));
+        assert_eq!(vector.max_item(), Some(&5));
+        assert_eq!(vector.min_by_key(|&x| 10 - x), Some(&5));
+        assert_eq!(vector.max_by_key(|&x| 10 - x), Some(&1));
+
+        let empty: ArrayVec<u32, 5> = ArrayVec::new();
+        assert_eq!(empty.min_item(), None);
+        assert_eq!(empty.max_item(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pad_to_past_capacity() {
+        let mut vector: ArrayVec<i32, 4> = ArrayVec::new();
+
+        vector.pad_to(5);
+    }
+
+    #[test]
+    fn test_as_full_array_mut() {
+        let mut vector: ArrayVec<u8, 2> = ArrayVec::new();
+        vector.push(1);
+        assert!(vector.as_full_array_mut().is_none());
+
+        vector.push(2);
+        vector.as_full_array_mut().unwrap()[1] = 42;
+        assert_eq!(vector.as_slice(), &[1, 42]);
+
+        // zero-capacity vectors are always full
+        let mut empty: ArrayVec<u8, 0> = ArrayVec::new();
+        assert_eq!(empty.as_full_array_mut(), Some(&mut []));
+    }
+
+    #[test]
+    fn test_drain_chunks_transfers_ownership() {
+        let counter = Cell::new(0);
+        let mut vector: ArrayVec<DropCounter<'_>, 5> = ArrayVec::new();
+        for _ in 0..5 {
+            vector.push(DropCounter(&counter));
+        }
+
+        {
+            let mut chunks = vector.drain_chunks::<2>();
+            assert_eq!(chunks.len(), 2);
+
+            let first = chunks.next().unwrap();
+            assert_eq!(counter.get(), 0);
+            drop(first);
+            assert_eq!(counter.get(), 2);
+        }
+
+        // the chunk we didn't take is left behind with the remainder
+        assert_eq!(counter.get(), 2);
+        assert_eq!(vector.len(), 3);
+
+        drop(vector);
+        assert_eq!(counter.get(), 5);
+    }
+
+    #[test]
+    fn test_recapacity_transfers_ownership() {
+        let counter = Cell::new(0);
+        let mut vector: ArrayVec<DropCounter<'_>, 4> = ArrayVec::new();
+        vector.push(DropCounter(&counter));
+        vector.push(DropCounter(&counter));
+
+        let vector: ArrayVec<_, 2> = vector.recapacity().unwrap();
+        assert_eq!(counter.get(), 0);
+
+        let vector = vector.recapacity::<1>().unwrap_err();
+        assert_eq!(counter.get(), 0);
+        assert_eq!(vector.len(), 2);
+
+        drop(vector);
+        assert_eq!(counter.get(), 2);
+    }
+
+    #[test]
+    fn test_entry_api() {
+        use crate::Entry;
+
+        let mut vector: ArrayVec<u32, 4> = ArrayVec::new();
+
+        match vector.entry(0) {
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.index(), 0);
+                entry.insert(1);
+            },
+            Entry::Occupied(_) => panic!("The vector is empty"),
+        }
+
+        match vector.entry(0) {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.get(), &1);
+                *entry.get_mut() = 5;
+            },
+            Entry::Vacant(_) => panic!("Index 0 should be occupied"),
+        }
+
+        *vector.entry(1).or_insert_with(|| 7) += 1;
+        assert_eq!(vector.as_slice(), &[5, 8]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_entry_past_the_end() {
+        let mut vector: ArrayVec<u32, 4> = ArrayVec::new();
+
+        let _ = vector.entry(1);
+    }
+
+    #[test]
+    fn test_sorted_insert_index() {
+        let vector = ArrayVec::from([1, 3, 3, 5]);
+
+        assert_eq!(vector.sorted_insert_index(&0), 0);
+        assert_eq!(vector.sorted_insert_index(&3), 3);
+        assert_eq!(vector.sorted_insert_index(&4), 3);
+        assert_eq!(vector.sorted_insert_index(&6), 4);
+    }
+
+    #[test]
+    fn test_drain_each_drops_remaining_items_when_callback_panics() {
+        extern crate std;
+
+        let counter = Cell::new(0);
+        let mut vector: ArrayVec<DropCounter<'_>, 4> = ArrayVec::new();
+        for _ in 0..4 {
+            vector.push(DropCounter(&counter));
+        }
+        let mut calls = 0;
+
+        let got =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                vector.drain_each(|item| {
+                    calls += 1;
+                    if calls == 2 {
+                        panic!("Oops");
+                    }
+                    drop(item);
+                });
+            }));
+
+        assert!(got.is_err());
+        assert!(vector.is_empty());
+        // every item was dropped exactly once
+        assert_eq!(counter.get(), 4);
+    }
+
+    #[test]
+    fn test_as_chunks_mut() {
+        let mut vector = ArrayVec::from([1, 2, 3, 4, 5]);
+
+        {
+            let (chunks, remainder) = vector.as_chunks_mut::<2>();
+            chunks[1] = [30, 40];
+            remainder[0] = 50;
+        }
+        assert_eq!(vector.as_slice(), &[1, 2, 30, 40, 50]);
+
+        let (chunks, remainder) = vector.as_chunks::<6>();
+        assert!(chunks.is_empty());
+        assert_eq!(remainder.len(), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_to_boxed_slice_transfers_ownership() {
+        let counter = Cell::new(0);
+        let mut vector: ArrayVec<DropCounter<'_>, 4> = ArrayVec::new();
+        for _ in 0..3 {
+            vector.push(DropCounter(&counter));
+        }
+
+        let boxed = vector.to_boxed_slice();
+        assert_eq!(counter.get(), 0);
+        assert_eq!(boxed.len(), 3);
+
+        drop(boxed);
+        assert_eq!(counter.get(), 3);
+    }
+
+    #[test]
+    fn test_ring_keeps_the_newest_items() {
+        use crate::RingArrayVec;
+
+        let mut ring: RingArrayVec<u32, 3> = RingArrayVec::new();
+        let mut evicted = ArrayVec::<u32, 5>::new();
+
+        for i in 0..5 {
+            if let Some(oldest) = ring.push_back(i) {
+                evicted.push(oldest);
+            }
+        }
+
+        assert_eq!(ring.len(), 3);
+        assert!(ring.iter().eq([2, 3, 4].iter()));
+        assert_eq!(evicted.as_slice(), &[0, 1]);
+        assert_eq!(ring.front(), Some(&2));
+        assert_eq!(ring.back(), Some(&4));
+
+        assert_eq!(ring.pop_front(), Some(2));
+        ring.push_back(5);
+        assert!(ring.iter().eq([3, 4, 5].iter()));
+    }
+
+    #[test]
+    fn test_ring_drops_items_once() {
+        use crate::RingArrayVec;
+
+        let counter = Cell::new(0);
+        let mut ring: RingArrayVec<DropCounter<'_>, 3> = RingArrayVec::new();
+
+        for _ in 0..5 {
+            drop(ring.push_back(DropCounter(&counter)));
+        }
+        assert_eq!(counter.get(), 2);
+
+        drop(ring.pop_front());
+        assert_eq!(counter.get(), 3);
+
+        drop(ring);
+        assert_eq!(counter.get(), 5);
+    }
+
+    #[test]
+    fn test_zero_capacity_ring() {
+        use crate::RingArrayVec;
+
+        let mut ring: RingArrayVec<u32, 0> = RingArrayVec::new();
+
+        assert_eq!(ring.push_back(1), Some(1));
+        assert!(ring.is_empty());
+        assert_eq!(ring.pop_front(), None);
+    }
+
+    #[test]
+    fn test_fnv1a_hash_of_empty_vector_is_the_offset_basis() {
+        let vector: ArrayVec<u8, 4> = ArrayVec::new();
+
+        assert_eq!(vector.fnv1a_hash(), 0xcbf29ce484222325);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "ArrayVec::split_at_mut(): index 5 is out of bounds in vector of length 4"
+    )]
+    fn test_split_at_mut_out_of_bounds() {
+        let mut vector = ArrayVec::from([1, 2, 3, 4]);
+
+        vector.split_at_mut(5);
+    }
+
+    #[test]
+    fn test_eviction_policies() {
+        let mut oldest: ArrayVec<u8, 3> = ArrayVec::new();
+        let mut newest: ArrayVec<u8, 3> = ArrayVec::new();
+
+        for i in 0..3 {
+            assert_eq!(oldest.push_or_evict_oldest(i), None);
+            assert_eq!(newest.push_or_evict_newest(i), None);
+        }
+
+        assert_eq!(oldest.push_or_evict_oldest(3), Some(0));
+        assert_eq!(oldest.push_or_evict_oldest(4), Some(1));
+        assert_eq!(oldest.as_slice(), &[2, 3, 4]);
+
+        assert_eq!(newest.push_or_evict_newest(3), Some(2));
+        assert_eq!(newest.push_or_evict_newest(4), Some(3));
+        assert_eq!(newest.as_slice(), &[0, 1, 4]);
+
+        let mut empty: ArrayVec<u8, 0> = ArrayVec::new();
+        assert_eq!(empty.push_or_evict_oldest(1), Some(1));
+        assert_eq!(empty.push_or_evict_newest(1), Some(1));
     }
-}
 
-impl<T, const N: usize> Deref for ArrayVec<T, { N }> {
-    type Target = [T];
+    #[test]
+    fn test_push_all_rotating_more_items_than_capacity() {
+        let mut vector: ArrayVec<u32, 2> = ArrayVec::new();
+        vector.push(1);
 
-    #[inline]
-    fn deref(&self) -> &Self::Target {
-        unsafe { slice::from_raw_parts(self.as_ptr(), self.len()) }
+        let evicted = vector.push_all_rotating([2, 3, 4]);
+
+        assert_eq!(vector.as_slice(), &[3, 4]);
+        assert_eq!(evicted.as_slice(), &[1, 2]);
     }
-}
 
-impl<T, const N: usize> DerefMut for ArrayVec<T, { N }> {
-    #[inline]
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len()) }
+    #[test]
+    fn test_put_integers() {
+        let mut buffer: ArrayVec<u8, 16> = ArrayVec::new();
+
+        buffer.put_u32_be(0x0102_0304).unwrap();
+        assert_eq!(buffer.as_slice(), &[0x01, 0x02, 0x03, 0x04]);
+
+        buffer.clear();
+        buffer.put_u16_be(0x0102).unwrap();
+        buffer.put_u16_le(0x0102).unwrap();
+        buffer.put_u32_le(0x0102_0304).unwrap();
+        buffer.put_u64_be(0x0102_0304_0506_0708).unwrap();
+        assert_eq!(
+            buffer.as_slice(),
+            &[1, 2, 2, 1, 4, 3, 2, 1, 1, 2, 3, 4, 5, 6, 7, 8]
+        );
+
+        // the buffer is full, so nothing else gets written
+        assert!(buffer.put_u16_le(0xFFFF).is_err());
+        assert_eq!(buffer.len(), 16);
     }
-}
 
-impl<T, const N: usize> Drop for ArrayVec<T, { N }> {
-    /// Makes sure all items are cleaned up once you're done with the
-    /// [`ArrayVec`].
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use core::{mem, sync::atomic::{AtomicUsize, Ordering}};
-    /// use const_arrayvec::ArrayVec;
-    ///
-    /// // create a dummy type which increments a number when dropped
-    ///
-    /// struct OnDropped<'a>(&'a AtomicUsize);
-    ///
-    /// impl<'a> Drop for OnDropped<'a> {
-    ///   fn drop(&mut self) { self.0.fetch_add(1, Ordering::Relaxed); }
-    /// }
-    ///
-    /// // create our vector
-    /// let mut vector: ArrayVec<OnDropped<'_>, 5> = ArrayVec::new();
-    ///
-    /// // then set up our counter
-    /// let counter = AtomicUsize::new(0);
-    ///
-    /// // and add a couple `OnDropped`'s to the vector
-    /// vector.push(OnDropped(&counter));
-    /// vector.push(OnDropped(&counter));
-    /// vector.push(OnDropped(&counter));
-    ///
-    /// // the vector is still live so our counter shouldn't have changed
-    /// assert_eq!(counter.load(Ordering::Relaxed), 0);
-    ///
-    /// // explicitly drop the vector
-    /// mem::drop(vector);
-    ///
-    /// // and the counter should have updated
-    /// assert_eq!(counter.load(Ordering::Relaxed), 3);
-    /// ```
-    #[inline]
-    fn drop(&mut self) {
-        // Makes sure the destructors for all items are run.
-        self.clear();
+    #[test]
+    fn test_read_back_written_integers() {
+        let mut buffer: ArrayVec<u8, 32> = ArrayVec::new();
+        buffer.put_u16_be(0x0102).unwrap();
+        buffer.put_u16_le(0x0304).unwrap();
+        buffer.put_u32_be(0x0506_0708).unwrap();
+        buffer.put_u32_le(0x090A_0B0C).unwrap();
+        buffer.put_u64_be(0x0D0E_0F10_1112_1314).unwrap();
+        buffer.put_u64_le(0x1516_1718_191A_1B1C).unwrap();
+
+        let mut reader = buffer.reader();
+
+        assert_eq!(reader.read_u16_be(), Ok(0x0102));
+        assert_eq!(reader.read_u16_le(), Ok(0x0304));
+        assert_eq!(reader.read_u32_be(), Ok(0x0506_0708));
+        assert_eq!(reader.read_u32_le(), Ok(0x090A_0B0C));
+        assert_eq!(reader.read_u64_be(), Ok(0x0D0E_0F10_1112_1314));
+        // there aren't enough bytes left, so the cursor shouldn't move
+        assert_eq!(reader.read_bytes(9), Err(()));
+        assert_eq!(reader.remaining().len(), 8);
+        assert_eq!(reader.read_u64_le(), Ok(0x1516_1718_191A_1B1C));
+        assert_eq!(reader.read_u8(), Err(()));
     }
-}
 
-impl<T, const N: usize> AsRef<[T]> for ArrayVec<T, { N }> {
-    #[inline]
-    fn as_ref(&self) -> &[T] { self.as_slice() }
-}
+    #[test]
+    fn test_retain_present_in_keeps_duplicates() {
+        let mut vector = ArrayVec::from([4, 1, 2, 4, 2]);
 
-impl<T, const N: usize> AsMut<[T]> for ArrayVec<T, { N }> {
-    #[inline]
-    fn as_mut(&mut self) -> &mut [T] { self.as_slice_mut() }
-}
+        vector.retain_present_in(&[2, 4]);
 
-impl<T: Debug, const N: usize> Debug for ArrayVec<T, { N }> {
-    #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        self.as_slice().fmt(f)
+        assert_eq!(vector.as_slice(), &[4, 2, 4, 2]);
     }
-}
 
-impl<T: PartialEq, const N: usize, const M: usize> PartialEq<ArrayVec<T, { M }>>
-    for ArrayVec<T, { N }>
-{
-    #[inline]
-    fn eq(&self, other: &ArrayVec<T, { M }>) -> bool {
-        self.as_slice() == other.as_slice()
+    #[test]
+    fn test_parts_mut() {
+        let mut vector: ArrayVec<u32, 4> = ArrayVec::new();
+        vector.push(1);
+        vector.push(2);
+
+        let (items, spare) = vector.parts_mut();
+        items[0] = 10;
+
+        assert_eq!(spare, 2);
+        assert_eq!(vector.as_slice(), &[10, 2]);
     }
-}
 
-impl<T: PartialEq, const N: usize> PartialEq<[T]> for ArrayVec<T, { N }> {
-    #[inline]
-    fn eq(&self, other: &[T]) -> bool { self.as_slice() == other }
-}
+    #[test]
+    fn test_capacity_assertions() {
+        type Buffer = ArrayVec<u8, 16>;
 
-impl<T: Eq, const N: usize> Eq for ArrayVec<T, { N }> {}
+        assert_capacity!(Buffer, 16);
+        assert_capacity!(ArrayVec<u32, 0>, 0);
 
-impl<T: PartialOrd, const N: usize> PartialOrd for ArrayVec<T, { N }> {
-    #[inline]
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.as_slice().partial_cmp(other.as_slice())
+        const CAPACITY: usize = crate::capacity_of::<u8, 16>();
+        assert_eq!(CAPACITY, Buffer::CAPACITY);
     }
-}
 
-impl<T: Ord, const N: usize> Ord for ArrayVec<T, { N }> {
-    #[inline]
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.as_slice().cmp(other.as_slice())
+    #[test]
+    fn test_drain_into_slice_transfers_ownership() {
+        let counter = Cell::new(0);
+        let mut vector: ArrayVec<DropCounter<'_>, 4> = ArrayVec::new();
+        for _ in 0..4 {
+            vector.push(DropCounter(&counter));
+        }
+        let other = Cell::new(0);
+        let mut dst = [DropCounter(&other), DropCounter(&other)];
+
+        assert_eq!(vector.drain_into_slice(&mut dst), 2);
+
+        // only the items which were overwritten got dropped
+        assert_eq!(counter.get(), 0);
+        assert_eq!(other.get(), 2);
+        assert_eq!(vector.len(), 2);
+
+        drop(dst);
+        assert_eq!(counter.get(), 2);
+        drop(vector);
+        assert_eq!(counter.get(), 4);
     }
-}
 
-impl<T: Hash, const N: usize> Hash for ArrayVec<T, { N }> {
-    #[inline]
-    fn hash<H: Hasher>(&self, hasher: &mut H) { self.as_slice().hash(hasher); }
-}
+    #[test]
+    fn test_set_len_checked() {
+        let mut vector: ArrayVec<u8, 4> = ArrayVec::new();
+        vector.try_extend_from_slice(&[1, 2, 3]).unwrap();
+        vector.clear_no_drop();
 
-impl<T, const N: usize> Default for ArrayVec<T, { N }> {
-    #[inline]
-    fn default() -> Self { ArrayVec::new() }
-}
+        unsafe {
+            // the items are still initialized, so it's fine to grow again
+            assert!(vector.set_len_checked(3).is_ok());
+            assert!(vector.set_len_checked(5).is_err());
+        }
 
-impl<Ix, T, const N: usize> Index<Ix> for ArrayVec<T, { N }>
-where
-    [T]: Index<Ix>,
-{
-    type Output = <[T] as Index<Ix>>::Output;
+        assert_eq!(vector.as_slice(), &[1, 2, 3]);
+    }
 
-    #[inline]
-    fn index(&self, ix: Ix) -> &Self::Output { self.as_slice().index(ix) }
-}
+    #[test]
+    fn test_from_array_const() {
+        const TABLE: ArrayVec<u8, 4> = ArrayVec::from_array_const([1, 2, 3, 4]);
+        const EMPTY: ArrayVec<u8, 0> = ArrayVec::from_array_const([]);
 
-impl<Ix, T, const N: usize> IndexMut<Ix> for ArrayVec<T, { N }>
-where
-    [T]: IndexMut<Ix>,
-{
-    #[inline]
-    fn index_mut(&mut self, ix: Ix) -> &mut Self::Output {
-        self.as_slice_mut().index_mut(ix)
+        assert_eq!(TABLE.len(), 4);
+        assert_eq!(TABLE.as_slice(), &[1, 2, 3, 4]);
+        assert!(EMPTY.is_empty());
     }
-}
 
-impl<T: Clone, const N: usize> Clone for ArrayVec<T, { N }> {
-    fn clone(&self) -> ArrayVec<T, { N }> {
-        let mut other: ArrayVec<T, { N }> = ArrayVec::new();
+    #[test]
+    fn test_first_duplicate_is_the_earliest_repeat() {
+        let vector = ArrayVec::from([1, 2, 2, 1]);
 
-        for item in self.as_slice() {
-            unsafe {
-                // if it fit into the original, it'll fit into the clone
-                other.push_unchecked(item.clone());
-            }
+        assert_eq!(vector.first_duplicate(), Some((1, 2)));
+    }
+
+    #[test]
+    fn test_take_transfers_ownership() {
+        let counter = Cell::new(0);
+        let mut vector: ArrayVec<DropCounter<'_>, 4> = ArrayVec::new();
+        for _ in 0..3 {
+            vector.push(DropCounter(&counter));
         }
 
-        other
+        let taken = vector.take();
+        assert_eq!(counter.get(), 0);
+        assert!(vector.is_empty());
+        assert_eq!(taken.len(), 3);
+
+        drop(vector);
+        assert_eq!(counter.get(), 0);
+        drop(taken);
+        assert_eq!(counter.get(), 3);
     }
-}
 
-impl<T, const N: usize> From<[T; N]> for ArrayVec<T, { N }> {
-    fn from(other: [T; N]) -> ArrayVec<T, { N }> {
-        let mut vec = ArrayVec::<T, { N }>::new();
+    #[test]
+    fn test_max_run_edge_cases() {
+        let empty: ArrayVec<u8, 4> = ArrayVec::new();
+        assert_eq!(empty.max_run(), 0);
 
-        unsafe {
-            // Copy the items from the array directly to the backing buffer
+        assert_eq!(ArrayVec::from([7]).max_run(), 1);
+        assert_eq!(ArrayVec::from([1, 2, 3]).max_run(), 1);
+        assert_eq!(ArrayVec::from([1, 2, 2, 2]).max_run(), 3);
+    }
 
-            // Note: Safe because a [T; N] is identical to [MaybeUninit<T>; N]
-            ptr::copy_nonoverlapping(
-                other.as_ptr(),
-                vec.as_mut_ptr(),
-                other.len(),
-            );
-            // ownership has been transferred to the backing buffer, make sure
-            // the original array's destructors aren't called prematurely
-            mem::forget(other);
-            // the memory has now been initialized so it's safe to set the
-            // length
-            vec.set_len(N);
-        }
+    #[test]
+    fn test_rle_round_trip() {
+        let vector = ArrayVec::from([7, 7, 7, 1, 2, 2, 7, 7]);
 
-        vec
+        let runs = vector.rle_encode::<4>().unwrap();
+        let decoded: ArrayVec<u32, 8> = ArrayVec::rle_decode(&runs).unwrap();
+
+        assert_eq!(decoded, vector);
     }
-}
 
-/// The error returned when there isn't enough space to add another item.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct CapacityError<T>(pub T);
+    #[test]
+    fn test_flatten_transfers_ownership() {
+        let drops = Cell::new(0);
+        let vector = ArrayVec::from([
+            [DropCounter(&drops), DropCounter(&drops)],
+            [DropCounter(&drops), DropCounter(&drops)],
+        ]);
 
-impl<T> Display for CapacityError<T> {
-    #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "Insufficient capacity")
+        let flat: ArrayVec<DropCounter<'_>, 5> = vector.flatten();
+
+        assert_eq!(flat.len(), 4);
+        assert_eq!(drops.get(), 0);
+        mem::drop(flat);
+        assert_eq!(drops.get(), 4);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::ArrayVec;
+    #[test]
+    fn test_shift_left_past_the_end_replaces_everything() {
+        let mut vector = ArrayVec::from([1, 2, 3]);
+
+        vector.shift_left(5, 9);
+
+        assert_eq!(&vector, [9, 9, 9].as_ref());
+    }
 
     #[test]
-    fn test_equal_to_expected_slice() {
-        let mut vector: ArrayVec<u8, 10> = ArrayVec::new();
-        vector.push(0);
-        vector.push(1);
-        vector.push(2);
-        assert_eq!(vector.len(), 3);
+    fn test_is_sorted_edge_cases() {
+        let empty: ArrayVec<u32, 4> = ArrayVec::new();
+        assert!(empty.is_sorted());
 
-        vector.try_insert(3, 3).unwrap();
+        let single = ArrayVec::from([42]);
+        assert!(single.is_sorted());
 
-        assert_eq!(vector.as_slice(), &[0, 1, 2, 3]);
-        assert_eq!(vector.capacity(), 10);
+        // NaN isn't ordered relative to anything
+        let with_nan = ArrayVec::from([1.0, f32::NAN, 2.0]);
+        assert!(!with_nan.is_sorted());
     }
 
     #[test]
-    fn test_force_insert_and_remove() {
-        let mut vector: ArrayVec<u8, 2> = ArrayVec::new();
+    fn test_try_extend_atomic_overflow_keeps_the_original_items() {
+        let drops = Cell::new(0);
+        let mut vector: ArrayVec<DropCounter<'_>, 3> = ArrayVec::new();
+        vector.push(DropCounter(&drops));
+        vector.push(DropCounter(&drops));
 
-        // force_insert
-        vector.force_insert(0, 2);
-        vector.force_insert(1, 4);
-        vector.force_insert(0, 4);
-        assert_eq!(vector.as_slice(), &[4, 2]);
+        let items = (0..3).map(|_| DropCounter(&drops));
+        let (_, pulled) = vector.try_extend_atomic(items).unwrap_err();
 
-        // remove
-        assert_eq!(vector.remove(0), 4);
-        assert_eq!(vector.as_slice(), &[2]);
-        assert_eq!(vector.try_remove(1), None);
-        assert_eq!(vector.remove(0), 2);
-        assert_eq!(vector.len(), 0);
+        assert_eq!(vector.len(), 2);
+        assert_eq!(pulled.len(), 1);
+        // only the item which didn't fit has been dropped
+        assert_eq!(drops.get(), 1);
 
-        // swap_remove
-        vector = ArrayVec::from([2, 4]);
-        assert_eq!(vector.swap_remove(0), 2);
-        assert_eq!(vector.as_slice(), &[4]);
-        assert_eq!(vector.try_swap_remove(1), None);
-        assert_eq!(vector.swap_remove(0), 4);
-        assert_eq!(vector.len(), 0);
+        mem::drop(pulled);
+        assert_eq!(drops.get(), 2);
+        mem::drop(vector);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn test_push_lots_of_repeated_bytes() {
+        let mut buffer: ArrayVec<u8, 128> = ArrayVec::new();
+        buffer.push(42);
+
+        buffer.try_push_repeated(0, 100).unwrap();
+
+        assert_eq!(buffer.len(), 101);
+        assert_eq!(buffer[0], 42);
+        assert!(buffer[1..].iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn test_find_subsequence_edge_cases() {
+        let vector = ArrayVec::from([1, 2, 3]);
+
+        assert_eq!(vector.find_subsequence(&[]), Some(0));
+        assert_eq!(vector.find_subsequence(&[1, 2, 3]), Some(0));
+        assert_eq!(vector.find_subsequence(&[1, 2, 3, 4]), None);
+    }
+
+    #[test]
+    fn test_dedup_all_drops_each_duplicate_once() {
+        #[derive(Debug)]
+        struct Tagged<'a> {
+            tag: u32,
+            _counter: DropCounter<'a>,
+        }
+
+        impl<'a> PartialEq for Tagged<'a> {
+            fn eq(&self, other: &Tagged<'a>) -> bool { self.tag == other.tag }
+        }
+
+        let drops = Cell::new(0);
+        let tagged = |tag| Tagged {
+            tag,
+            _counter: DropCounter(&drops),
+        };
+        let mut vector = ArrayVec::from([
+            tagged(1),
+            tagged(2),
+            tagged(1),
+            tagged(3),
+            tagged(2),
+        ]);
+
+        vector.dedup_all();
+
+        assert!(vector.iter().map(|item| item.tag).eq(1..=3));
+        assert_eq!(drops.get(), 2);
+        mem::drop(vector);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn test_transfer_front_transfers_ownership() {
+        let drops = Cell::new(0);
+        let mut vector = ArrayVec::from([
+            DropCounter(&drops),
+            DropCounter(&drops),
+            DropCounter(&drops),
+        ]);
+        let mut dst: ArrayVec<DropCounter<'_>, 4> = ArrayVec::new();
+
+        vector.transfer_front(2, &mut dst).unwrap();
+
+        assert_eq!(vector.len(), 1);
+        assert_eq!(dst.len(), 2);
+        assert_eq!(drops.get(), 0);
+
+        mem::drop(vector);
+        assert_eq!(drops.get(), 1);
+        mem::drop(dst);
+        assert_eq!(drops.get(), 3);
     }
 }
@@ -0,0 +1,205 @@
+use core::{
+    fmt::{self, Debug, Formatter},
+    iter::Chain,
+    mem::MaybeUninit,
+    ptr, slice,
+};
+
+/// A fixed-capacity ring buffer which overwrites its oldest item when a new
+/// one is pushed while full.
+///
+/// Items are never shifted around in memory, so pushing to the back and
+/// popping from the front are both `O(1)`.
+///
+/// # Examples
+///
+/// ```rust
+/// use const_arrayvec::RingArrayVec;
+/// let mut ring: RingArrayVec<u32, 3> = RingArrayVec::new();
+///
+/// for i in 0..5 {
+///     ring.push_back(i);
+/// }
+///
+/// assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+/// assert_eq!(ring.pop_front(), Some(2));
+/// ```
+pub struct RingArrayVec<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    /// The index of the oldest item.
+    head: usize,
+    length: usize,
+}
+
+impl<T, const N: usize> RingArrayVec<T, { N }> {
+    /// Create a new, empty [`RingArrayVec`].
+    #[inline]
+    pub const fn new() -> RingArrayVec<T, { N }> {
+        unsafe {
+            RingArrayVec {
+                // Note: an array of uninitialized items is always valid
+                items: MaybeUninit::uninit().assume_init(),
+                head: 0,
+                length: 0,
+            }
+        }
+    }
+
+    #[inline]
+    pub const fn len(&self) -> usize { self.length }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool { self.len() == 0 }
+
+    #[inline]
+    pub const fn capacity(&self) -> usize { N }
+
+    #[inline]
+    pub const fn is_full(&self) -> bool { self.len() >= self.capacity() }
+
+    /// Add an item to the back of the buffer, removing and returning the
+    /// oldest item if the buffer was already full.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use const_arrayvec::RingArrayVec;
+    /// let mut ring: RingArrayVec<u32, 2> = RingArrayVec::new();
+    ///
+    /// assert_eq!(ring.push_back(1), None);
+    /// assert_eq!(ring.push_back(2), None);
+    /// assert_eq!(ring.push_back(3), Some(1));
+    /// ```
+    pub fn push_back(&mut self, item: T) -> Option<T> {
+        if N == 0 {
+            // there's nowhere to put it, so the new item is the oldest
+            return Some(item);
+        }
+
+        unsafe {
+            if self.is_full() {
+                // overwrite the oldest item and move the head along
+                let slot = self.slot(0);
+                let evicted = slot.read();
+                slot.write(item);
+                self.head = (self.head + 1) % N;
+
+                Some(evicted)
+            } else {
+                self.slot(self.length).write(item);
+                self.length += 1;
+
+                None
+            }
+        }
+    }
+
+    /// Remove the oldest item from the buffer.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        unsafe {
+            let item = self.slot(0).read();
+            self.head = (self.head + 1) % N;
+            self.length -= 1;
+
+            Some(item)
+        }
+    }
+
+    /// Get a reference to the oldest item.
+    #[inline]
+    pub fn front(&self) -> Option<&T> { self.iter().next() }
+
+    /// Get a reference to the newest item.
+    #[inline]
+    pub fn back(&self) -> Option<&T> { self.iter().next_back() }
+
+    /// Get the items as a pair of slices which, when concatenated, are in
+    /// order from oldest to newest.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.ranges();
+        let start = self.items.as_ptr() as *const T;
+
+        unsafe {
+            (
+                slice::from_raw_parts(start.add(front.0), front.1),
+                slice::from_raw_parts(start, back),
+            )
+        }
+    }
+
+    /// The mutable version of [`RingArrayVec::as_slices()`].
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.ranges();
+        let start = self.items.as_mut_ptr() as *mut T;
+
+        unsafe {
+            // Note: the two ranges never overlap
+            (
+                slice::from_raw_parts_mut(start.add(front.0), front.1),
+                slice::from_raw_parts_mut(start, back),
+            )
+        }
+    }
+
+    /// Iterate over the items from oldest to newest.
+    #[inline]
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back.iter())
+    }
+
+    /// Remove all items from the buffer.
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let front: *mut [T] = front;
+        let back: *mut [T] = back;
+
+        // "pre-poop our pants" in case a destructor panics
+        self.head = 0;
+        self.length = 0;
+
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+
+    /// A pointer to the slot holding the `index`'th oldest item.
+    unsafe fn slot(&mut self, index: usize) -> *mut T {
+        let start = self.items.as_mut_ptr() as *mut T;
+        start.add((self.head + index) % N)
+    }
+
+    /// The `(start, length)` of the items from the head to the end of the
+    /// backing array, and the number of items which wrapped around to the
+    /// start.
+    fn ranges(&self) -> ((usize, usize), usize) {
+        let tail_room = N - self.head;
+
+        if self.length <= tail_room {
+            ((self.head, self.length), 0)
+        } else {
+            ((self.head, tail_room), self.length - tail_room)
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for RingArrayVec<T, { N }> {
+    #[inline]
+    fn drop(&mut self) { self.clear(); }
+}
+
+impl<T, const N: usize> Default for RingArrayVec<T, { N }> {
+    #[inline]
+    fn default() -> Self { RingArrayVec::new() }
+}
+
+impl<T: Debug, const N: usize> Debug for RingArrayVec<T, { N }> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,49 @@
+use core::{
+    fmt::{self, Debug, Display, Formatter, Write},
+    str,
+};
+
+/// A helper for displaying a byte buffer as UTF-8, substituting
+/// `U+FFFD REPLACEMENT CHARACTER` for any invalid sequences.
+///
+/// This is created by [`crate::ArrayVec::display_utf8_lossy()`] and never
+/// allocates.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Utf8Lossy<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Utf8Lossy<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self { Utf8Lossy { bytes } }
+}
+
+impl<'a> Display for Utf8Lossy<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut remaining = self.bytes;
+
+        loop {
+            match str::from_utf8(remaining) {
+                Ok(valid) => return f.write_str(valid),
+                Err(e) => {
+                    let (valid, rest) = remaining.split_at(e.valid_up_to());
+                    // Safety: from_utf8() told us this much is valid
+                    f.write_str(unsafe { str::from_utf8_unchecked(valid) })?;
+                    f.write_char(char::REPLACEMENT_CHARACTER)?;
+
+                    match e.error_len() {
+                        // skip past the invalid sequence and keep going
+                        Some(len) => remaining = &rest[len..],
+                        // the input ended part-way through a sequence
+                        None => return Ok(()),
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<'a> Debug for Utf8Lossy<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"", self)
+    }
+}